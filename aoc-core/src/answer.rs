@@ -0,0 +1,61 @@
+use std::fmt;
+
+use serde::Serialize;
+
+use crate::Part;
+
+/// A puzzle answer. Most days produce a number, some produce text.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+#[serde(untagged)]
+pub enum Answer {
+    Number(i64),
+    Text(String),
+}
+
+impl fmt::Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Answer::Number(value) => value.fmt(f),
+            Answer::Text(value) => value.fmt(f),
+        }
+    }
+}
+
+macro_rules! answer_from_number {
+    ($($t:ty),*) => {
+        $(impl From<$t> for Answer {
+            fn from(value: $t) -> Answer {
+                Answer::Number(value as i64)
+            }
+        })*
+    };
+}
+
+answer_from_number!(i32, i64, u32, usize);
+
+/// Values too large for a number answer are kept exactly as text.
+impl From<u64> for Answer {
+    fn from(value: u64) -> Answer {
+        match i64::try_from(value) {
+            Ok(value) => Answer::Number(value),
+            Err(_) => Answer::Text(value.to_string()),
+        }
+    }
+}
+
+impl From<String> for Answer {
+    fn from(value: String) -> Answer {
+        Answer::Text(value)
+    }
+}
+
+impl From<&str> for Answer {
+    fn from(value: &str) -> Answer {
+        Answer::Text(value.to_string())
+    }
+}
+
+/// Formats the answer for a single part the same way for every day.
+pub fn format_answer(part: Part, answer: &Answer) -> String {
+    return format!("Answer for part {} is {}", part, answer);
+}
@@ -0,0 +1,122 @@
+use std::fs;
+use std::io;
+use std::path::{Path, PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::input::input_hash;
+use crate::{Answer, Error, Part, Solution};
+
+/// The answers file shared by `aoc run --record`, `aoc run --check` and the
+/// test suites, at the root of the workspace.
+pub fn default_path() -> PathBuf {
+    let workspace = Path::new(env!("CARGO_MANIFEST_DIR")).parent().unwrap();
+    return workspace.join("answers.toml");
+}
+
+/// Answers known to be right, keyed by day, part and a hash of the input they
+/// were produced from.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Answers {
+    #[serde(default, rename = "answer")]
+    entries: Vec<Recorded>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq, Serialize, Deserialize)]
+pub struct Recorded {
+    pub day: u8,
+    pub part: u8,
+    pub input: String,
+    pub answer: String,
+}
+
+/// How an answer compares with the recorded one.
+#[derive(Debug, PartialEq, Eq)]
+pub enum Check {
+    Match,
+    Mismatch { expected: String },
+    /// Nothing was recorded for this day, part and input.
+    Missing,
+}
+
+impl Answers {
+    /// Loads the answers at `path`. A file that does not exist yet holds no answers.
+    pub fn load(path: &Path) -> Result<Answers, Error> {
+        let content = match fs::read_to_string(path) {
+            Ok(content) => content,
+            Err(err) if err.kind() == io::ErrorKind::NotFound => return Ok(Answers::default()),
+            Err(source) => return Err(Error::Io { path: path.to_path_buf(), source }),
+        };
+
+        return toml::from_str(&content).map_err(|err| Error::Store { path: path.to_path_buf(), message: err.to_string() });
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let content = toml::to_string(self).map_err(|err| Error::Store { path: path.to_path_buf(), message: err.to_string() })?;
+        return fs::write(path, content).map_err(|source| Error::Io { path: path.to_path_buf(), source });
+    }
+
+    pub fn get(&self, day: u8, part: Part, input_hash: &str) -> Option<&str> {
+        return self
+            .entries
+            .iter()
+            .find(|r| r.day == day && r.part == part.number() && r.input == input_hash)
+            .map(|r| r.answer.as_str());
+    }
+
+    /// Records `answer`, replacing whatever was recorded for the same day,
+    /// part and input. Entries are kept sorted so the file diffs cleanly.
+    pub fn record(&mut self, day: u8, part: Part, input_hash: &str, answer: &Answer) {
+        self.entries.retain(|r| !(r.day == day && r.part == part.number() && r.input == input_hash));
+        self.entries.push(Recorded { day, part: part.number(), input: input_hash.to_string(), answer: answer.to_string() });
+        self.entries.sort_by(|a, b| (a.day, a.part, &a.input).cmp(&(b.day, b.part, &b.input)));
+    }
+
+    pub fn check(&self, day: u8, part: Part, input_hash: &str, answer: &Answer) -> Check {
+        match self.get(day, part, input_hash) {
+            Some(expected) if expected == answer.to_string() => Check::Match,
+            Some(expected) => Check::Mismatch { expected: expected.to_string() },
+            None => Check::Missing,
+        }
+    }
+}
+
+/// Solves `part` of `input` and panics unless the answer matches the one in
+/// the workspace answers file. Meant for the day crates' tests.
+pub fn assert_recorded<S: Solution>(part: Part, input: &str) {
+    let answers = Answers::load(&default_path()).unwrap();
+    let answer = S::solve(part, input).unwrap();
+
+    match answers.check(S::DAY, part, &input_hash(input), &answer) {
+        Check::Match => {}
+        Check::Mismatch { expected } => panic!("Day {} part {} gave {} but {} is recorded", S::DAY, part, answer, expected),
+        Check::Missing => panic!("No answer recorded for day {} part {}, run `aoc run {} --record`", S::DAY, part, S::DAY),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn records_and_checks_answers_by_input() {
+        let mut answers = Answers::default();
+        answers.record(8, Part::One, "abc", &Answer::from(1700));
+
+        assert_eq!(answers.check(8, Part::One, "abc", &Answer::from(1700)), Check::Match);
+        assert_eq!(answers.check(8, Part::One, "abc", &Answer::from(1699)), Check::Mismatch { expected: String::from("1700") });
+        assert_eq!(answers.check(8, Part::One, "def", &Answer::from(1700)), Check::Missing);
+    }
+
+    #[test]
+    fn round_trips_through_toml() {
+        let mut answers = Answers::default();
+        answers.record(5, Part::Two, "abc", &Answer::from("MCD"));
+        answers.record(5, Part::Two, "abc", &Answer::from("CMZ"));
+
+        let content = toml::to_string(&answers).unwrap();
+        let loaded: Answers = toml::from_str(&content).unwrap();
+
+        assert_eq!(loaded.entries, vec![Recorded { day: 5, part: 2, input: String::from("abc"), answer: String::from("CMZ") }]);
+    }
+}
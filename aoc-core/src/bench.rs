@@ -0,0 +1,144 @@
+use std::fs;
+use std::hint::black_box;
+use std::path::Path;
+use std::time::{Duration, Instant};
+
+use serde::{Deserialize, Serialize};
+
+use crate::Error;
+
+/// Every stage is run at least this many times, however long it takes.
+const MIN_ITERATIONS: u32 = 3;
+
+/// Timings of one stage, repeated until its time budget ran out.
+#[derive(Debug, Clone, Copy)]
+pub struct Sample {
+    pub iterations: u32,
+    pub median: Duration,
+    pub min: Duration,
+    pub mean: Duration,
+}
+
+/// Runs `f` repeatedly for roughly `budget`. Only the call itself is timed;
+/// dropping what it returned is not.
+pub fn measure<R, F: FnMut() -> R>(budget: Duration, mut f: F) -> Sample {
+    let mut times: Vec<Duration> = Vec::new();
+    let started = Instant::now();
+
+    while times.len() < MIN_ITERATIONS as usize || started.elapsed() < budget {
+        let start = Instant::now();
+        let output = black_box(f());
+        times.push(start.elapsed());
+        drop(output);
+    }
+
+    times.sort();
+    let total: Duration = times.iter().sum();
+
+    return Sample {
+        iterations: times.len() as u32,
+        median: times[times.len() / 2],
+        min: times[0],
+        mean: total / times.len() as u32,
+    };
+}
+
+/// One benchmarked stage of a day, as stored in a baseline file.
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Measurement {
+    pub day: u8,
+    /// `parse`, `part1` or `part2`.
+    pub stage: String,
+    /// The hash of the input, so that timings of different inputs are never compared.
+    pub input: String,
+    pub bytes: usize,
+    pub iterations: u32,
+    pub median_ns: u64,
+    pub min_ns: u64,
+    pub mean_ns: u64,
+}
+
+impl Measurement {
+    pub fn new(day: u8, stage: impl Into<String>, input: &str, bytes: usize, sample: &Sample) -> Measurement {
+        return Measurement {
+            day,
+            stage: stage.into(),
+            input: input.to_string(),
+            bytes,
+            iterations: sample.iterations,
+            median_ns: sample.median.as_nanos() as u64,
+            min_ns: sample.min.as_nanos() as u64,
+            mean_ns: sample.mean.as_nanos() as u64,
+        };
+    }
+
+    /// Median time spent on each byte of input.
+    pub fn ns_per_byte(&self) -> f64 {
+        return self.median_ns as f64 / self.bytes.max(1) as f64;
+    }
+
+    /// Median throughput in megabytes of input per second.
+    pub fn megabytes_per_second(&self) -> f64 {
+        return self.bytes as f64 / (self.median_ns.max(1) as f64 / 1e9) / 1e6;
+    }
+}
+
+/// A saved set of measurements that later runs are compared against.
+#[derive(Debug, Default, Serialize, Deserialize)]
+pub struct Baseline {
+    pub measurements: Vec<Measurement>,
+}
+
+impl Baseline {
+    pub fn load(path: &Path) -> Result<Baseline, Error> {
+        let content = fs::read_to_string(path).map_err(|source| Error::Io { path: path.to_path_buf(), source })?;
+        return serde_json::from_str(&content).map_err(|err| Error::Store { path: path.to_path_buf(), message: err.to_string() });
+    }
+
+    pub fn save(&self, path: &Path) -> Result<(), Error> {
+        let content = serde_json::to_string_pretty(self).map_err(|err| Error::Store { path: path.to_path_buf(), message: err.to_string() })?;
+        return fs::write(path, content).map_err(|source| Error::Io { path: path.to_path_buf(), source });
+    }
+
+    /// The baseline measurement of the same day and stage on the same input.
+    pub fn find(&self, measurement: &Measurement) -> Option<&Measurement> {
+        return self
+            .measurements
+            .iter()
+            .find(|m| m.day == measurement.day && m.stage == measurement.stage && m.input == measurement.input);
+    }
+
+    /// How much slower `measurement` is than its baseline, as a fraction of
+    /// the baseline median. Negative when it got faster.
+    pub fn change(&self, measurement: &Measurement) -> Option<f64> {
+        return self
+            .find(measurement)
+            .map(|baseline| (measurement.median_ns as f64 - baseline.median_ns as f64) / baseline.median_ns.max(1) as f64);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn measurement(stage: &str, median_ns: u64) -> Measurement {
+        return Measurement { day: 1, stage: String::from(stage), input: String::from("abc"), bytes: 100, iterations: 3, median_ns, min_ns: median_ns, mean_ns: median_ns };
+    }
+
+    #[test]
+    fn measures_at_least_the_minimum_iterations() {
+        let sample = measure(Duration::ZERO, || 1 + 1);
+
+        assert_eq!(sample.iterations, MIN_ITERATIONS);
+        assert!(sample.min <= sample.median);
+    }
+
+    #[test]
+    fn compares_with_the_same_stage_of_the_baseline() {
+        let baseline = Baseline { measurements: vec![measurement("parse", 1000), measurement("part1", 2000)] };
+
+        assert_eq!(baseline.change(&measurement("part1", 3000)), Some(0.5));
+        assert_eq!(baseline.change(&measurement("part2", 3000)), None);
+        assert_eq!(measurement("parse", 1000).ns_per_byte(), 10.0);
+    }
+}
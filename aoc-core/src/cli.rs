@@ -0,0 +1,134 @@
+//! Command line arguments shared by the day binaries. Each binary wraps these
+//! in its own `Cli` so that `--help` shows the right program name.
+
+use std::io::BufRead;
+use std::path::PathBuf;
+
+use clap::Args;
+
+use crate::answer::format_answer;
+use crate::recovery::skipped_report;
+use crate::report::{self, Format};
+use crate::run::Run;
+use crate::{input, Error, ParseError, ParseMode, Part, Recovery, Solution};
+
+/// A puzzle input given as a path on the command line.
+#[derive(Args, Debug)]
+pub struct InputArgs {
+    /// Path to the puzzle input, or `-` to read it from stdin
+    pub path: PathBuf,
+}
+
+impl InputArgs {
+    pub fn read(&self) -> Result<String, Error> {
+        return input::read_input(&self.path);
+    }
+
+    pub fn open(&self) -> Result<Box<dyn BufRead>, Error> {
+        return input::open_input(&self.path);
+    }
+}
+
+/// A puzzle input path that can be left out when the binary has an input
+/// built in through its `embedded-input` feature.
+#[derive(Args, Debug)]
+pub struct EmbeddedInputArgs {
+    /// Path to the puzzle input, or `-` to read it from stdin. Defaults to the
+    /// embedded input when the binary was built with one
+    pub path: Option<PathBuf>,
+}
+
+impl EmbeddedInputArgs {
+    pub fn read(&self, embedded: Option<&str>) -> Result<String, Error> {
+        match (&self.path, embedded) {
+            (Some(path), _) => input::read_input(path),
+            (None, Some(input)) => Ok(input.to_string()),
+            (None, None) => Err(Error::MissingInput),
+        }
+    }
+}
+
+/// An optional `--part` flag. Binaries keep their own default output when it
+/// is left out.
+#[derive(Args, Debug)]
+pub struct PartArgs {
+    /// Only print the answer for this part, either 1 or 2
+    #[arg(long, short)]
+    pub part: Option<Part>,
+}
+
+impl PartArgs {
+    /// The selected part, or both when none was given.
+    pub fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+}
+
+/// Solves one part from an already parsed input and prints it the same way
+/// for every day.
+pub fn print_part<S: Solution>(part: Part, parsed: &S::Parsed) {
+    match S::solve_parsed(part, parsed) {
+        Ok(answer) => println!("{}", format_answer(part, &answer)),
+        Err(err) => println!("{}", err),
+    }
+}
+
+/// A `--strict` flag choosing how bad input lines are handled.
+#[derive(Args, Debug)]
+pub struct ParseArgs {
+    /// Fail on the first line that cannot be parsed instead of skipping it
+    #[arg(long)]
+    pub strict: bool,
+}
+
+impl ParseArgs {
+    pub fn mode(&self) -> ParseMode {
+        if self.strict {
+            return ParseMode::Strict;
+        }
+        return ParseMode::Lenient;
+    }
+
+    /// Parses `input` in the selected mode. Lines skipped by a lenient parse
+    /// are reported on stderr.
+    pub fn parse_input<S: Solution>(&self, input: &str) -> Result<S::Parsed, ParseError> {
+        let mut recovery = Recovery::new(self.mode());
+        let parsed = S::parse_with(input, &mut recovery);
+
+        if let Some(report) = skipped_report(recovery.skipped()) {
+            eprintln!("{}", report);
+        }
+
+        return parsed;
+    }
+}
+
+/// A `--format` flag for printing answers with their timings.
+#[derive(Args, Debug)]
+pub struct FormatArgs {
+    /// Print the answers with their timings and input hash in this format
+    #[arg(long, value_enum)]
+    pub format: Option<Format>,
+}
+
+/// Solves `parts` of `input` and prints them in `format`. Skipped lines and
+/// parse errors go to stderr so they never mix with the report.
+pub fn print_report<S: Solution>(input: &str, parts: &[Part], mode: ParseMode, format: Format) {
+    print_run(input, &crate::run::run::<S>(input, parts, mode), format);
+}
+
+/// Prints a finished `run` of `input` the way [`print_report`] does, for days
+/// that need to look at the run afterwards.
+pub fn print_run(input: &str, run: &Run, format: Format) {
+    println!("{}", report::render(format, std::slice::from_ref(run)));
+
+    if let Some(report) = skipped_report(&run.skipped) {
+        eprintln!("{}", report);
+    }
+    if let Err(err @ Error::Parse(_)) = &run.parse {
+        eprintln!("{}", err.render(input));
+    }
+}
@@ -0,0 +1,130 @@
+//! Parse errors that point at the offending part of the puzzle input.
+
+use std::fmt;
+
+/// Where in the input a parse error happened. Lines and columns start at 1,
+/// and columns count characters rather than bytes.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Span {
+    pub line: usize,
+    pub column: usize,
+    /// The text that could not be parsed.
+    pub text: String,
+}
+
+/// Why a day's puzzle input could not be parsed.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ParseError {
+    pub message: String,
+    pub span: Option<Span>,
+}
+
+impl ParseError {
+    /// An error that is not tied to any particular part of the input.
+    pub fn new(message: impl Into<String>) -> ParseError {
+        return ParseError { message: message.into(), span: None };
+    }
+
+    pub fn at(line: usize, column: usize, text: impl Into<String>, message: impl Into<String>) -> ParseError {
+        return ParseError { message: message.into(), span: Some(Span { line, column, text: text.into() }) };
+    }
+
+    /// An error for `token`, which must be a slice of `source`. The line and
+    /// column are worked out from where `token` sits inside `source`; if it
+    /// does not come from `source` the error has no span.
+    pub fn spanning(source: &str, token: &str, message: impl Into<String>) -> ParseError {
+        let start = source.as_ptr() as usize;
+        let offset = (token.as_ptr() as usize).wrapping_sub(start);
+
+        if offset > source.len() || offset + token.len() > source.len() {
+            return ParseError::new(message);
+        }
+
+        let before = &source[..offset];
+        let line = before.matches('\n').count() + 1;
+        let line_start = before.rfind('\n').map(|i| i + 1).unwrap_or(0);
+        let column = source[line_start..offset].chars().count() + 1;
+
+        return ParseError::at(line, column, token, message);
+    }
+
+    /// Moves the error to `line` of a larger input, for parsers that only see
+    /// one line at a time.
+    pub fn on_line(mut self, line: usize) -> ParseError {
+        if let Some(span) = &mut self.span {
+            span.line = line;
+        }
+        return self;
+    }
+
+    /// Renders the error with the offending line of `source` and a caret
+    /// underline, for example:
+    ///
+    /// ```text
+    /// error: Unknown shape D
+    ///  --> line 3, column 1
+    ///   |
+    /// 3 | D Y
+    ///   | ^
+    /// ```
+    pub fn render(&self, source: &str) -> String {
+        let mut rendered = format!("error: {}", self.message);
+
+        if let Some(span) = &self.span {
+            rendered.push_str(&format!("\n --> line {}, column {}", span.line, span.column));
+
+            if let Some(line) = source.lines().nth(span.line - 1) {
+                let gutter = " ".repeat(span.line.to_string().len());
+                let underline = "^".repeat(span.text.chars().count().max(1));
+                let indent = " ".repeat(span.column - 1);
+
+                rendered.push_str(&format!("\n{} |\n{} | {}\n{} | {}{}", gutter, span.line, line, gutter, indent, underline));
+            }
+        }
+
+        return rendered;
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.span {
+            Some(span) => write!(f, "{} at line {}, column {}", self.message, span.line, span.column),
+            None => write!(f, "{}", self.message),
+        }
+    }
+}
+
+impl std::error::Error for ParseError {}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn locates_token_inside_source() {
+        let source = "A Y\nB X\nC Q\n";
+        let token = &source[10..11];
+
+        let err = ParseError::spanning(source, token, "Unknown response Q");
+
+        assert_eq!(err.span, Some(Span { line: 3, column: 3, text: String::from("Q") }));
+    }
+
+    #[test]
+    fn has_no_span_for_foreign_token() {
+        let err = ParseError::spanning("A Y", "Q", "Unknown response Q");
+        assert_eq!(err.span, None);
+    }
+
+    #[test]
+    fn renders_caret_under_offending_text() {
+        let source = "move 1 from 2 to 1\nmove x from 1 to 3\n";
+        let err = ParseError::at(2, 6, "x", "Expected a number");
+
+        assert_eq!(
+            err.render(source),
+            "error: Expected a number\n --> line 2, column 6\n  |\n2 | move x from 1 to 3\n  |      ^"
+        );
+    }
+}
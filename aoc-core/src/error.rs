@@ -0,0 +1,62 @@
+use std::fmt;
+use std::io;
+use std::path::PathBuf;
+
+use crate::input::STDIN;
+use crate::ParseError;
+
+/// Errors shared by every day's solution.
+#[derive(Debug)]
+pub enum Error {
+    /// The puzzle input could not be read.
+    Io { path: PathBuf, source: io::Error },
+    /// No input path was given and the binary has no input built in.
+    MissingInput,
+    /// The puzzle input was read but could not be understood.
+    Parse(ParseError),
+    /// The input parsed but no answer could be produced from it.
+    Solve(String),
+    /// A file the runner keeps results in, such as the recorded answers or a
+    /// benchmark baseline, could not be understood or written.
+    Store { path: PathBuf, message: String },
+}
+
+impl fmt::Display for Error {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Error::Io { path, source } if path.as_os_str() == STDIN => write!(f, "Could not read stdin: {}", source),
+            Error::Io { path, source } => write!(f, "Could not read file {}: {}", path.display(), source),
+            Error::MissingInput => write!(f, "No input given and this binary was built without an embedded input"),
+            Error::Parse(err) => write!(f, "Failed to parse input: {}", err),
+            Error::Solve(message) => write!(f, "Failed to solve puzzle: {}", message),
+            Error::Store { path, message } => write!(f, "Could not use {}: {}", path.display(), message),
+        }
+    }
+}
+
+impl std::error::Error for Error {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Error::Io { source, .. } => Some(source),
+            Error::Parse(err) => Some(err),
+            _ => None,
+        }
+    }
+}
+
+impl From<ParseError> for Error {
+    fn from(err: ParseError) -> Error {
+        return Error::Parse(err);
+    }
+}
+
+impl Error {
+    /// Like `Display`, but parse errors also show the offending line of
+    /// `source` with the bad text underlined.
+    pub fn render(&self, source: &str) -> String {
+        match self {
+            Error::Parse(err) => err.render(source),
+            other => other.to_string(),
+        }
+    }
+}
@@ -0,0 +1,46 @@
+use std::fs::{self, File};
+use std::io::{self, BufRead, BufReader, Read};
+use std::path::Path;
+
+use crate::Error;
+
+/// The path that means "read the puzzle input from stdin".
+pub const STDIN: &str = "-";
+
+/// Reads the puzzle input at `path` into memory, or from stdin when `path` is `-`.
+pub fn read_input(path: &Path) -> Result<String, Error> {
+    if path == Path::new(STDIN) {
+        let mut input = String::new();
+        return io::stdin()
+            .read_to_string(&mut input)
+            .map(|_| input)
+            .map_err(|source| Error::Io { path: path.to_path_buf(), source });
+    }
+
+    return fs::read_to_string(path).map_err(|source| Error::Io { path: path.to_path_buf(), source });
+}
+
+/// Opens the puzzle input at `path` for reading line by line, without
+/// loading it into memory. Reads stdin when `path` is `-`.
+pub fn open_input(path: &Path) -> Result<Box<dyn BufRead>, Error> {
+    if path == Path::new(STDIN) {
+        return Ok(Box::new(io::stdin().lock()));
+    }
+
+    let file = File::open(path).map_err(|source| Error::Io { path: path.to_path_buf(), source })?;
+    return Ok(Box::new(BufReader::new(file)));
+}
+
+/// A short fingerprint of `input`, so answers can be tied to the input they
+/// came from. This is 64-bit FNV-1a, which is stable across builds and
+/// platforms, unlike the standard library's hasher.
+pub fn input_hash(input: &str) -> String {
+    let mut hash: u64 = 0xcbf29ce484222325;
+
+    for byte in input.bytes() {
+        hash ^= byte as u64;
+        hash = hash.wrapping_mul(0x100000001b3);
+    }
+
+    return format!("{:016x}", hash);
+}
@@ -0,0 +1,23 @@
+//! Shared building blocks for the Advent of Code 2022 solutions: input loading,
+//! part selection, error types and answer formatting.
+
+pub mod answer;
+pub mod answers;
+pub mod bench;
+pub mod cli;
+pub mod diagnostic;
+pub mod error;
+pub mod input;
+pub mod part;
+pub mod recovery;
+pub mod report;
+pub mod run;
+pub mod solution;
+
+pub use answer::Answer;
+pub use diagnostic::{ParseError, Span};
+pub use error::Error;
+pub use part::Part;
+pub use recovery::{ParseMode, Recovery};
+pub use run::Runnable;
+pub use solution::Solution;
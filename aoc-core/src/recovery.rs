@@ -0,0 +1,101 @@
+use crate::ParseError;
+
+/// What a parser does with a line it cannot understand.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default)]
+pub enum ParseMode {
+    /// Skip the line and keep going. Skipped lines are collected so they can
+    /// be reported afterwards.
+    #[default]
+    Lenient,
+    /// Stop at the first bad line.
+    Strict,
+}
+
+/// Collects the lines a lenient parse skipped. Parsers hand every line's
+/// result to [`Recovery::recover`] rather than dropping errors themselves.
+#[derive(Debug, Default)]
+pub struct Recovery {
+    mode: ParseMode,
+    skipped: Vec<ParseError>,
+}
+
+impl Recovery {
+    pub fn new(mode: ParseMode) -> Recovery {
+        return Recovery { mode, skipped: Vec::new() };
+    }
+
+    pub fn strict() -> Recovery {
+        return Recovery::new(ParseMode::Strict);
+    }
+
+    pub fn lenient() -> Recovery {
+        return Recovery::new(ParseMode::Lenient);
+    }
+
+    pub fn mode(&self) -> ParseMode {
+        return self.mode;
+    }
+
+    /// Passes errors on in strict mode. In lenient mode the error is recorded
+    /// and `Ok(None)` is returned so the caller can skip the line.
+    pub fn recover<T>(&mut self, result: Result<T, ParseError>) -> Result<Option<T>, ParseError> {
+        match (result, self.mode) {
+            (Ok(value), _) => Ok(Some(value)),
+            (Err(err), ParseMode::Strict) => Err(err),
+            (Err(err), ParseMode::Lenient) => {
+                self.skipped.push(err);
+                Ok(None)
+            }
+        }
+    }
+
+    pub fn skipped(&self) -> &[ParseError] {
+        return &self.skipped;
+    }
+
+    pub fn into_skipped(self) -> Vec<ParseError> {
+        return self.skipped;
+    }
+}
+
+/// Summarises what a lenient parse skipped, or `None` if nothing was.
+pub fn skipped_report(skipped: &[ParseError]) -> Option<String> {
+    if skipped.is_empty() {
+        return None;
+    }
+
+    let noun = if skipped.len() == 1 { "line" } else { "lines" };
+    let mut report = format!("warning: skipped {} {} that could not be parsed, use --strict to stop at the first one", skipped.len(), noun);
+
+    for err in skipped {
+        report.push_str(&format!("\n  {}", err));
+    }
+
+    return Some(report);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn strict_recovery_passes_errors_on() {
+        let mut recovery = Recovery::strict();
+
+        assert!(recovery.recover::<i32>(Err(ParseError::new("bad"))).is_err());
+        assert!(recovery.skipped().is_empty());
+    }
+
+    #[test]
+    fn lenient_recovery_records_skipped_lines() {
+        let mut recovery = Recovery::lenient();
+
+        assert_eq!(recovery.recover(Ok(1)), Ok(Some(1)));
+        assert_eq!(recovery.recover::<i32>(Err(ParseError::at(2, 1, "x", "bad"))), Ok(None));
+        assert_eq!(recovery.skipped().len(), 1);
+        assert_eq!(
+            skipped_report(recovery.skipped()).unwrap(),
+            "warning: skipped 1 line that could not be parsed, use --strict to stop at the first one\n  bad at line 2, column 1"
+        );
+    }
+}
@@ -0,0 +1,157 @@
+use std::time::Duration;
+
+use clap::ValueEnum;
+use serde::Serialize;
+
+use crate::run::Run;
+use crate::Answer;
+
+/// How the results of a run are printed.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, ValueEnum)]
+pub enum Format {
+    /// An aligned table for reading in a terminal
+    #[default]
+    Text,
+    /// A JSON array with one object per part
+    Json,
+    /// Comma separated values with a header row
+    Csv,
+}
+
+/// One row of a report: a solved part, or a day whose input failed to parse.
+#[derive(Debug, Serialize)]
+pub struct Record {
+    pub day: u8,
+    /// Missing when the input could not be parsed, since no part was run.
+    pub part: Option<u8>,
+    pub answer: Option<Answer>,
+    pub error: Option<String>,
+    pub parse_ns: Option<u64>,
+    pub solve_ns: Option<u64>,
+    pub input_hash: String,
+}
+
+pub fn records(runs: &[Run]) -> Vec<Record> {
+    return runs.iter().flat_map(run_records).collect();
+}
+
+fn run_records(run: &Run) -> Vec<Record> {
+    match &run.parse {
+        Ok(parse_time) => {
+            return run
+                .parts
+                .iter()
+                .map(|part_run| Record {
+                    day: run.day,
+                    part: Some(part_run.part.number()),
+                    answer: part_run.answer.as_ref().ok().cloned(),
+                    error: part_run.answer.as_ref().err().map(|err| err.to_string()),
+                    parse_ns: Some(parse_time.as_nanos() as u64),
+                    solve_ns: Some(part_run.elapsed.as_nanos() as u64),
+                    input_hash: run.input_hash.clone(),
+                })
+                .collect();
+        }
+        Err(err) => {
+            return vec![Record { day: run.day, part: None, answer: None, error: Some(err.to_string()), parse_ns: None, solve_ns: None, input_hash: run.input_hash.clone() }];
+        }
+    }
+}
+
+/// Renders `runs` in `format`. Every binary prints its results through here.
+pub fn render(format: Format, runs: &[Run]) -> String {
+    let records = records(runs);
+
+    match format {
+        Format::Text => render_text(&records),
+        Format::Json => serde_json::to_string_pretty(&records).unwrap(),
+        Format::Csv => render_csv(&records),
+    }
+}
+
+pub fn format_duration(duration: Duration) -> String {
+    return format!("{:.2?}", duration);
+}
+
+fn render_text(records: &[Record]) -> String {
+    let answers: Vec<String> = records
+        .iter()
+        .map(|r| match (&r.answer, &r.error) {
+            (Some(answer), _) => answer.to_string(),
+            (None, Some(err)) => err.clone(),
+            (None, None) => String::new(),
+        })
+        .collect();
+    let width = answers.iter().map(|a| a.len()).chain(std::iter::once("Answer".len())).max().unwrap_or(0);
+    let nanos = |ns: Option<u64>| ns.map(|ns| format_duration(Duration::from_nanos(ns))).unwrap_or_else(|| String::from("-"));
+
+    let mut lines = vec![format!("{:>3}  {:>4}  {:<width$}  {:>12}  {:>12}", "Day", "Part", "Answer", "Parse", "Solve", width = width)];
+    for (record, answer) in records.iter().zip(answers) {
+        let part = record.part.map(|p| p.to_string()).unwrap_or_else(|| String::from("-"));
+        lines.push(format!("{:>3}  {:>4}  {:<width$}  {:>12}  {:>12}", record.day, part, answer, nanos(record.parse_ns), nanos(record.solve_ns), width = width));
+    }
+
+    return lines.join("\n");
+}
+
+fn render_csv(records: &[Record]) -> String {
+    let optional = |value: Option<String>| value.unwrap_or_default();
+
+    let mut lines = vec![String::from("day,part,answer,error,parse_ns,solve_ns,input_hash")];
+    for r in records {
+        let fields = [
+            r.day.to_string(),
+            optional(r.part.map(|p| p.to_string())),
+            csv_field(&optional(r.answer.as_ref().map(|a| a.to_string()))),
+            csv_field(&optional(r.error.clone())),
+            optional(r.parse_ns.map(|ns| ns.to_string())),
+            optional(r.solve_ns.map(|ns| ns.to_string())),
+            r.input_hash.clone(),
+        ];
+        lines.push(fields.join(","));
+    }
+
+    return lines.join("\n");
+}
+
+/// Quotes `value` if it would otherwise break the row.
+fn csv_field(value: &str) -> String {
+    if value.contains([',', '"', '\n']) {
+        return format!("\"{}\"", value.replace('"', "\"\""));
+    }
+    return value.to_string();
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::run::PartRun;
+    use crate::{Error, Part};
+
+    fn sample_runs() -> Vec<Run> {
+        let parts = vec![
+            PartRun { part: Part::One, answer: Ok(Answer::from(24000)), elapsed: Duration::from_nanos(20) },
+            PartRun { part: Part::Two, answer: Err(Error::Solve(String::from("no elves, none at all"))), elapsed: Duration::from_nanos(30) },
+        ];
+        return vec![Run { day: 1, input_hash: String::from("abc"), parse: Ok(Duration::from_nanos(10)), skipped: Vec::new(), parts }];
+    }
+
+    #[test]
+    fn renders_csv_with_quoted_fields() {
+        let csv = render(Format::Csv, &sample_runs());
+
+        assert_eq!(
+            csv,
+            "day,part,answer,error,parse_ns,solve_ns,input_hash\n1,1,24000,,10,20,abc\n1,2,,\"Failed to solve puzzle: no elves, none at all\",10,30,abc"
+        );
+    }
+
+    #[test]
+    fn renders_json_answers_as_numbers_or_text() {
+        let json: serde_json::Value = serde_json::from_str(&render(Format::Json, &sample_runs())).unwrap();
+
+        assert_eq!(json[0]["answer"], 24000);
+        assert_eq!(json[0]["input_hash"], "abc");
+        assert!(json[1]["answer"].is_null());
+    }
+}
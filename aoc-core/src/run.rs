@@ -0,0 +1,156 @@
+use std::path::PathBuf;
+use std::time::{Duration, Instant};
+
+use crate::bench::{measure, Measurement};
+
+use crate::input::input_hash;
+use crate::{Answer, Error, ParseError, ParseMode, Part, Recovery, Solution};
+
+/// An object-safe view of a [`Solution`], so that every day can be held in
+/// one list and run without knowing its `Parsed` type.
+pub trait Runnable {
+    fn day(&self) -> u8;
+
+    fn default_input(&self) -> PathBuf;
+
+    /// Parses `input` once and solves each of `parts` from it, timing every stage.
+    fn run(&self, input: &str, parts: &[Part], mode: ParseMode) -> Run;
+
+    /// Times parsing `input` and then solving each of `parts` from it, giving
+    /// every stage roughly `budget` of repeated runs.
+    fn bench(&self, input: &str, parts: &[Part], budget: Duration) -> Result<Vec<Measurement>, Error>;
+}
+
+/// The outcome of running one day.
+pub struct Run {
+    pub day: u8,
+    /// The hash of the input, as used by the answers file.
+    pub input_hash: String,
+    /// How long parsing took, or why it failed. No parts are run when it fails.
+    pub parse: Result<Duration, Error>,
+    /// Lines a lenient parse skipped.
+    pub skipped: Vec<ParseError>,
+    pub parts: Vec<PartRun>,
+}
+
+/// The outcome of solving one part from an already parsed input.
+pub struct PartRun {
+    pub part: Part,
+    pub answer: Result<Answer, Error>,
+    pub elapsed: Duration,
+}
+
+impl<T: Solution> Runnable for T {
+    fn day(&self) -> u8 {
+        return T::DAY;
+    }
+
+    fn default_input(&self) -> PathBuf {
+        return T::default_input();
+    }
+
+    fn run(&self, input: &str, parts: &[Part], mode: ParseMode) -> Run {
+        return run::<T>(input, parts, mode);
+    }
+
+    fn bench(&self, input: &str, parts: &[Part], budget: Duration) -> Result<Vec<Measurement>, Error> {
+        let hash = input_hash(input);
+        let parsed = T::parse(input)?;
+        let mut measurements = vec![Measurement::new(T::DAY, "parse", &hash, input.len(), &measure(budget, || T::parse(input)))];
+
+        for &part in parts {
+            T::solve_parsed(part, &parsed)?;
+            let sample = measure(budget, || T::solve_parsed(part, &parsed));
+            measurements.push(Measurement::new(T::DAY, format!("part{}", part.number()), &hash, input.len(), &sample));
+        }
+
+        return Ok(measurements);
+    }
+}
+
+/// Parses `input` once and solves each of `parts` from it, timing every stage.
+pub fn run<T: Solution>(input: &str, parts: &[Part], mode: ParseMode) -> Run {
+    let mut recovery = Recovery::new(mode);
+    let start = Instant::now();
+    let parsed = T::parse_with(input, &mut recovery);
+    let parse_time = start.elapsed();
+    let skipped = recovery.into_skipped();
+
+    match parsed {
+        Ok(parsed) => {
+            let parts = parts
+                .iter()
+                .map(|&part| {
+                    let start = Instant::now();
+                    let answer = T::solve_parsed(part, &parsed);
+                    return PartRun { part, answer, elapsed: start.elapsed() };
+                })
+                .collect();
+
+            return Run { day: T::DAY, input_hash: input_hash(input), parse: Ok(parse_time), skipped, parts };
+        }
+        Err(err) => {
+            return Run { day: T::DAY, input_hash: input_hash(input), parse: Err(err.into()), skipped, parts: Vec::new() };
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ParseError;
+
+    struct Sum;
+
+    impl Solution for Sum {
+        type Parsed = Vec<i64>;
+
+        const DAY: u8 = 99;
+
+        fn default_input() -> PathBuf {
+            return PathBuf::new();
+        }
+
+        fn parse_with(input: &str, recovery: &mut Recovery) -> Result<Vec<i64>, ParseError> {
+            return input
+                .split_whitespace()
+                .map(|n| recovery.recover(n.parse::<i64>().map_err(|e| ParseError::new(e.to_string()))))
+                .filter_map(|n| n.transpose())
+                .collect();
+        }
+
+        fn part1(numbers: &Vec<i64>) -> Result<Answer, Error> {
+            return Ok(numbers.iter().sum::<i64>().into());
+        }
+
+        fn part2(numbers: &Vec<i64>) -> Result<Answer, Error> {
+            return Ok(numbers.iter().product::<i64>().into());
+        }
+    }
+
+    #[test]
+    fn runs_every_part_from_one_parse() {
+        let run = Sum.run("2 3 4", &Part::ALL, ParseMode::Strict);
+
+        assert_eq!(run.day, 99);
+        assert!(run.parse.is_ok());
+        let answers: Vec<Answer> = run.parts.into_iter().map(|p| p.answer.unwrap()).collect();
+        assert_eq!(answers, vec![Answer::Number(9), Answer::Number(24)]);
+    }
+
+    #[test]
+    fn skips_parts_when_parsing_fails() {
+        let run = Sum.run("2 x 4", &Part::ALL, ParseMode::Strict);
+
+        assert!(matches!(run.parse, Err(Error::Parse(_))));
+        assert!(run.parts.is_empty());
+    }
+
+    #[test]
+    fn reports_lines_skipped_by_a_lenient_parse() {
+        let run = Sum.run("2 x 4", &[Part::One], ParseMode::Lenient);
+
+        assert_eq!(run.skipped.len(), 1);
+        assert_eq!(run.parts[0].answer.as_ref().unwrap(), &Answer::Number(6));
+    }
+}
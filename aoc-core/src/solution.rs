@@ -0,0 +1,42 @@
+use std::path::PathBuf;
+
+use crate::{Answer, Error, ParseError, Part, Recovery};
+
+/// A single day's puzzle, split into a parse stage and one solve stage per
+/// part. Both parts share the parsed value, so the input is only parsed once.
+pub trait Solution {
+    /// The puzzle input in the shape both parts work from.
+    type Parsed;
+
+    /// The day of December the puzzle was released on.
+    const DAY: u8;
+
+    /// The input used when none is given on the command line.
+    fn default_input() -> PathBuf;
+
+    /// Parses `input`, handing each line's errors to `recovery`, which decides
+    /// whether a bad line stops the parse or is skipped.
+    fn parse_with(input: &str, recovery: &mut Recovery) -> Result<Self::Parsed, ParseError>;
+
+    /// Parses `input`, stopping at the first bad line.
+    fn parse(input: &str) -> Result<Self::Parsed, ParseError> {
+        return Self::parse_with(input, &mut Recovery::strict());
+    }
+
+    fn part1(parsed: &Self::Parsed) -> Result<Answer, Error>;
+
+    fn part2(parsed: &Self::Parsed) -> Result<Answer, Error>;
+
+    fn solve_parsed(part: Part, parsed: &Self::Parsed) -> Result<Answer, Error> {
+        match part {
+            Part::One => Self::part1(parsed),
+            Part::Two => Self::part2(parsed),
+        }
+    }
+
+    /// Parses `input` and solves a single part of it.
+    fn solve(part: Part, input: &str) -> Result<Answer, Error> {
+        let parsed = Self::parse(input)?;
+        return Self::solve_parsed(part, &parsed);
+    }
+}
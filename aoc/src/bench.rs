@@ -0,0 +1,121 @@
+use std::path::PathBuf;
+use std::process::ExitCode;
+use std::time::Duration;
+
+use aoc_core::bench::{Baseline, Measurement};
+use aoc_core::input::read_input;
+use aoc_core::report::format_duration;
+use clap::Args;
+
+use crate::SelectArgs;
+
+#[derive(Args)]
+pub struct BenchArgs {
+    #[command(flatten)]
+    select: SelectArgs,
+
+    /// How long to keep repeating each stage, in milliseconds
+    #[arg(long, default_value_t = 1000)]
+    time: u64,
+
+    /// Save the measurements as a JSON baseline
+    #[arg(long)]
+    save: Option<PathBuf>,
+
+    /// Compare the measurements with a saved baseline and fail on any regression
+    #[arg(long)]
+    baseline: Option<PathBuf>,
+
+    /// How much slower than the baseline, in percent, counts as a regression
+    #[arg(long, default_value_t = 10.0)]
+    threshold: f64,
+}
+
+pub fn bench_solutions(args: &BenchArgs) -> ExitCode {
+    let selected = match args.select.solutions() {
+        Ok(selected) => selected,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let parts = args.select.parts();
+
+    let baseline = match &args.baseline {
+        Some(path) => match Baseline::load(path) {
+            Ok(baseline) => Some(baseline),
+            Err(err) => {
+                eprintln!("{}", err);
+                return ExitCode::FAILURE;
+            }
+        },
+        None => None,
+    };
+
+    let budget = Duration::from_millis(args.time);
+    let mut measurements: Vec<Measurement> = Vec::new();
+
+    for solution in selected {
+        let path = args.select.input_path(solution);
+        let result = read_input(&path).and_then(|input| solution.bench(&input, &parts, budget));
+
+        match result {
+            Ok(mut day_measurements) => measurements.append(&mut day_measurements),
+            Err(err) => {
+                eprintln!("Day {}: {}", solution.day(), err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    print_table(&measurements, baseline.as_ref());
+
+    if let Some(path) = &args.save {
+        if let Err(err) = (Baseline { measurements: measurements.clone() }).save(path) {
+            eprintln!("{}", err);
+            return ExitCode::FAILURE;
+        }
+    }
+
+    if let Some(baseline) = &baseline {
+        let threshold = args.threshold / 100.0;
+        let regressions: Vec<(&Measurement, f64)> = measurements
+            .iter()
+            .filter_map(|m| baseline.change(m).map(|change| (m, change)))
+            .filter(|&(_, change)| change > threshold)
+            .collect();
+
+        for (measurement, change) in &regressions {
+            eprintln!("\nDay {} {} regressed by {:.1}%", measurement.day, measurement.stage, change * 100.0);
+        }
+
+        if !regressions.is_empty() {
+            return ExitCode::FAILURE;
+        }
+    }
+
+    return ExitCode::SUCCESS;
+}
+
+fn print_table(measurements: &[Measurement], baseline: Option<&Baseline>) {
+    println!("{:>3}  {:<5}  {:>6}  {:>12}  {:>12}  {:>10}  {:>10}  {:>8}", "Day", "Stage", "Runs", "Median", "Min", "ns/byte", "MB/s", "Change");
+
+    for m in measurements {
+        let change = match baseline.and_then(|b| b.change(m)) {
+            Some(change) => format!("{:+.1}%", change * 100.0),
+            None => String::from("-"),
+        };
+
+        println!(
+            "{:>3}  {:<5}  {:>6}  {:>12}  {:>12}  {:>10.2}  {:>10.2}  {:>8}",
+            m.day,
+            m.stage,
+            m.iterations,
+            format_duration(Duration::from_nanos(m.median_ns)),
+            format_duration(Duration::from_nanos(m.min_ns)),
+            m.ns_per_byte(),
+            m.megabytes_per_second(),
+            change
+        );
+    }
+}
@@ -0,0 +1,215 @@
+mod bench;
+mod solutions;
+
+use std::path::{Path, PathBuf};
+use std::process::ExitCode;
+
+use aoc_core::answers::{self, Answers, Check};
+use aoc_core::cli::{FormatArgs, ParseArgs};
+use aoc_core::input::read_input;
+use aoc_core::recovery::skipped_report;
+use aoc_core::report;
+use aoc_core::run::Run;
+use aoc_core::{Error, Part, Runnable};
+use clap::{Args, Parser, Subcommand};
+
+fn main() -> ExitCode {
+    let args = Cli::parse();
+
+    match args.command {
+        Command::Run(run) => run_solutions(&run),
+        Command::Bench(bench) => bench::bench_solutions(&bench),
+    }
+}
+
+#[derive(Parser)]
+#[command(name = "aoc", about = "Runs the Advent of Code 2022 solutions")]
+struct Cli {
+    #[command(subcommand)]
+    command: Command,
+}
+
+#[derive(Subcommand)]
+enum Command {
+    /// Solve a single day, or every day with --all
+    Run(RunArgs),
+    /// Time the parse and solve stages of a day, or of every day with --all
+    Bench(bench::BenchArgs),
+}
+
+/// Which days, parts and input a command works on.
+#[derive(Args)]
+struct SelectArgs {
+    /// Day to solve
+    #[arg(required_unless_present = "all", conflicts_with = "all")]
+    day: Option<u8>,
+
+    /// Solve every day
+    #[arg(long)]
+    all: bool,
+
+    /// Only solve this part, either 1 or 2. Both parts are solved by default
+    #[arg(long, short)]
+    part: Option<Part>,
+
+    /// Puzzle input to use instead of the day's default input
+    #[arg(long, short, conflicts_with = "all")]
+    input: Option<PathBuf>,
+}
+
+impl SelectArgs {
+    fn solutions(&self) -> Result<Vec<&'static dyn Runnable>, String> {
+        match self.day {
+            Some(day) => match solutions::find(day) {
+                Some(solution) => Ok(vec![solution]),
+                None => Err(format!("There is no solution for day {}", day)),
+            },
+            None => Ok(solutions::SOLUTIONS.to_vec()),
+        }
+    }
+
+    fn parts(&self) -> Vec<Part> {
+        match self.part {
+            Some(part) => vec![part],
+            None => Part::ALL.to_vec(),
+        }
+    }
+
+    fn input_path(&self, solution: &dyn Runnable) -> PathBuf {
+        return self.input.clone().unwrap_or_else(|| solution.default_input());
+    }
+}
+
+#[derive(Args)]
+struct RunArgs {
+    #[command(flatten)]
+    select: SelectArgs,
+
+    #[command(flatten)]
+    parse: ParseArgs,
+
+    #[command(flatten)]
+    format: FormatArgs,
+
+    /// Save the answers to the answers file
+    #[arg(long, conflicts_with = "check")]
+    record: bool,
+
+    /// Compare the answers with the answers file and fail on any mismatch
+    #[arg(long)]
+    check: bool,
+
+    /// Answers file to use with --record and --check
+    #[arg(long)]
+    answers: Option<PathBuf>,
+}
+
+fn run_solutions(args: &RunArgs) -> ExitCode {
+    let selected = match args.select.solutions() {
+        Ok(selected) => selected,
+        Err(message) => {
+            eprintln!("{}", message);
+            return ExitCode::FAILURE;
+        }
+    };
+    let parts = args.select.parts();
+
+    let mut runs: Vec<Run> = Vec::new();
+    let mut diagnostics: Vec<String> = Vec::new();
+
+    for solution in selected {
+        let path = args.select.input_path(solution);
+
+        match read_input(&path) {
+            Ok(input) => {
+                let run = solution.run(&input, &parts, args.parse.mode());
+
+                if let Some(report) = skipped_report(&run.skipped) {
+                    diagnostics.push(format!("Day {}: {}", run.day, report));
+                }
+
+                if let Err(err @ Error::Parse(_)) = &run.parse {
+                    diagnostics.push(format!("Day {}: {}", run.day, err.render(&input)));
+                }
+
+                runs.push(run);
+            }
+            Err(err) => {
+                eprintln!("Day {}: {}", solution.day(), err);
+                return ExitCode::FAILURE;
+            }
+        }
+    }
+
+    println!("{}", report::render(args.format.format.unwrap_or_default(), &runs));
+
+    for diagnostic in diagnostics {
+        eprintln!("\n{}", diagnostic);
+    }
+
+    let mut failed = runs.iter().any(|r| r.parse.is_err() || r.parts.iter().any(|p| p.answer.is_err()));
+
+    if args.record || args.check {
+        let path = args.answers.clone().unwrap_or_else(answers::default_path);
+
+        let result = match args.record {
+            true => record_answers(&path, &runs),
+            false => check_answers(&path, &runs),
+        };
+
+        match result {
+            Ok(matched) => failed |= !matched,
+            Err(err) => {
+                eprintln!("{}", err);
+                failed = true;
+            }
+        }
+    }
+
+    if failed {
+        return ExitCode::FAILURE;
+    }
+    return ExitCode::SUCCESS;
+}
+
+/// Saves every answer that was found to the answers file at `path`.
+fn record_answers(path: &Path, runs: &[Run]) -> Result<bool, Error> {
+    let mut answers = Answers::load(path)?;
+
+    for run in runs {
+        for part_run in &run.parts {
+            if let Ok(answer) = &part_run.answer {
+                answers.record(run.day, part_run.part, &run.input_hash, answer);
+            }
+        }
+    }
+
+    answers.save(path)?;
+    eprintln!("\nRecorded answers in {}", path.display());
+    return Ok(true);
+}
+
+/// Compares every answer with the answers file at `path`. Returns whether
+/// they all matched; answers that were never recorded are reported but do
+/// not count as a mismatch.
+fn check_answers(path: &Path, runs: &[Run]) -> Result<bool, Error> {
+    let answers = Answers::load(path)?;
+    let mut matched = true;
+
+    for run in runs {
+        for part_run in &run.parts {
+            if let Ok(answer) = &part_run.answer {
+                match answers.check(run.day, part_run.part, &run.input_hash, answer) {
+                    Check::Match => {}
+                    Check::Mismatch { expected } => {
+                        eprintln!("\nDay {} part {}: expected {} but got {}", run.day, part_run.part, expected, answer);
+                        matched = false;
+                    }
+                    Check::Missing => eprintln!("\nDay {} part {}: no answer recorded for this input", run.day, part_run.part),
+                }
+            }
+        }
+    }
+
+    return Ok(matched);
+}
@@ -0,0 +1,30 @@
+use aoc_core::Runnable;
+
+/// Every day that has a solution, in order.
+pub const SOLUTIONS: [&dyn Runnable; 8] = [
+    &day_1::Puzzle,
+    &day_2::Puzzle,
+    &day_3::Puzzle,
+    &day_4::Puzzle,
+    &day_5::Puzzle,
+    &day_6::Puzzle,
+    &day_7::Puzzle,
+    &day_8::Puzzle,
+];
+
+pub fn find(day: u8) -> Option<&'static dyn Runnable> {
+    return SOLUTIONS.iter().copied().find(|s| s.day() == day);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn finds_every_day_by_number() {
+        for day in 1..=8 {
+            assert_eq!(find(day).map(|s| s.day()), Some(day));
+        }
+        assert!(find(9).is_none());
+    }
+}
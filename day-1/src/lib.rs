@@ -0,0 +1,694 @@
+use std::cmp::{Ordering, Reverse};
+use std::collections::{BTreeMap, BinaryHeap};
+use std::str::FromStr;
+use std::io::BufRead;
+use std::mem;
+use std::path::{Path, PathBuf};
+use aoc_core::{Answer, Error, ParseError, Recovery, Solution};
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Elves;
+
+    const DAY: u8 = 1;
+
+    fn default_input() -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join("day1.input");
+    }
+
+    fn parse_with(input: &str, recovery: &mut Recovery) -> Result<Elves, ParseError> {
+        return parse_input(input, recovery);
+    }
+
+    fn part1(elves: &Elves) -> Result<Answer, Error> {
+        let top_calories = elves.get_top_elves(1).first().map(|e| e.calories).unwrap_or(0);
+        return Ok(top_calories.into());
+    }
+
+    fn part2(elves: &Elves) -> Result<Answer, Error> {
+        return match total_calories(elves.get_top_elves(3).iter().map(|e| e.calories)) {
+            Some(combined_calories) => Ok(combined_calories.into()),
+            None => Err(Error::Solve(format!("The top three elves carry more than {} calories between them", Calories::MAX))),
+        };
+    }
+}
+
+/// The calories carried by an item, an elf or a group of elves.
+pub type Calories = u64;
+
+/// Adds up `calories`, or `None` when the total does not fit in [`Calories`].
+pub fn total_calories(calories: impl IntoIterator<Item = Calories>) -> Option<Calories> {
+    return calories.into_iter().try_fold(0, |total: Calories, item| total.checked_add(item));
+}
+
+/// Reads every elf's items. Blank lines at the end of the input do not start
+/// another elf.
+pub fn parse_input(value: &str, recovery: &mut Recovery) -> Result<Elves, ParseError> {
+    let value_end = value.trim_end();
+    if value_end.is_empty() {
+        return Ok(Elves { elves: Vec::new() });
+    }
+
+    let lines: Vec<&str> = value_end.split("\n").collect();
+    let segments = lines.split(|f| f.trim().is_empty());
+    let elves = segments.into_iter().enumerate().map(|(index, items)| {
+        return convert_elf(value, index + 1, items, recovery);
+    });
+    return Ok(Elves { elves: elves.collect::<Result<Vec<Elf>, ParseError>>()? });
+}
+
+/// Reads the calories of an elf's items. Items that are not numbers are
+/// handed to `recovery`, so a lenient parse leaves them out. An item that
+/// takes the elf's total past [`Calories::MAX`] is always an error.
+fn convert_elf(source: &str, number: usize, items: &[&str], recovery: &mut Recovery) -> Result<Elf, ParseError>
+{
+    let mut elf = Elf { number, calories: 0, items: Vec::new() };
+
+    for item in items.iter().map(|item| item.trim()) {
+        if let Some(calories) = recovery.recover(parse_calories(source, item))? {
+            elf.calories = elf.calories.checked_add(calories).ok_or_else(|| overflow_error(source, item, number))?;
+            elf.items.push(calories);
+        }
+    }
+
+    return Ok(elf);
+}
+
+/// Parses a single item, which must be a slice of `source`.
+fn parse_calories(source: &str, item: &str) -> Result<Calories, ParseError> {
+    return item
+        .parse::<Calories>()
+        .map_err(|_| ParseError::spanning(source, item, format!("Expected a calorie count but found {:?}", item)));
+}
+
+/// The error for `item`, which must be a slice of `source`, taking the total
+/// of elf `number` past what [`Calories`] can hold.
+fn overflow_error(source: &str, item: &str, number: usize) -> ParseError {
+    return ParseError::spanning(source, item, format!("Elf {} carries more than {} calories in total", number, Calories::MAX));
+}
+
+/// Reads an inventory from `reader` one line at a time and keeps only the
+/// `count` elves carrying the most calories, most first. Memory use depends on
+/// `count` and the size of the largest elf, not on the size of the inventory, and the
+/// result matches [`Elves::get_top_elves`] on the same input. `path` is only
+/// used to report read errors.
+pub fn stream_top_elves<R: BufRead>(mut reader: R, path: &Path, count: usize, recovery: &mut Recovery) -> Result<Vec<Elf>, Error> {
+    // A min-heap of the best elves so far, so the weakest is the one dropped.
+    let mut heap: BinaryHeap<Reverse<Elf>> = BinaryHeap::new();
+    let mut keep = |elf: Elf| {
+        heap.push(Reverse(elf));
+        if heap.len() > count {
+            heap.pop();
+        }
+    };
+
+    let mut line = String::new();
+    let mut line_number = 0;
+    let mut number = 1;
+    let mut items: Vec<Calories> = Vec::new();
+    let mut total: Calories = 0;
+    // Blank lines only end an elf once another item follows, so that blank
+    // lines at the end of the input do not start an empty elf.
+    let mut blank_lines = 0;
+    let mut any_items = false;
+
+    loop {
+        line.clear();
+        let read = reader.read_line(&mut line).map_err(|source| Error::Io { path: path.to_path_buf(), source })?;
+        if read == 0 {
+            break;
+        }
+
+        line_number += 1;
+        let item = line.trim();
+
+        if item.is_empty() {
+            blank_lines += 1;
+            continue;
+        }
+
+        for _ in 0..mem::take(&mut blank_lines) {
+            keep(Elf { number, calories: mem::take(&mut total), items: mem::take(&mut items) });
+            number += 1;
+        }
+        any_items = true;
+
+        if let Some(calories) = recovery.recover(parse_calories(&line, item).map_err(|err| err.on_line(line_number)))? {
+            total = total.checked_add(calories).ok_or_else(|| overflow_error(&line, item, number).on_line(line_number))?;
+            items.push(calories);
+        }
+    }
+
+    if any_items {
+        keep(Elf { number, calories: total, items });
+    }
+
+    return Ok(heap.into_sorted_vec().into_iter().map(|Reverse(elf)| elf).collect());
+}
+
+pub struct Elves {
+    pub elves: Vec<Elf>
+}
+
+#[derive(Clone)]
+pub struct Elf {
+    pub number: usize,
+    /// The total of `items`.
+    pub calories: Calories,
+    /// The calories of every item the elf carries, in input order.
+    pub items: Vec<Calories>,
+}
+
+impl Elf {
+    /// An elf carrying `items`, or `None` when their total does not fit in
+    /// [`Calories`].
+    pub fn new(number: usize, items: Vec<Calories>) -> Option<Elf> {
+        let calories = total_calories(items.iter().copied())?;
+        return Some(Elf { number, calories, items });
+    }
+
+    pub fn largest_item(&self) -> Option<Calories> {
+        return self.items.iter().max().copied();
+    }
+
+    /// The items carrying more than `threshold` calories.
+    pub fn items_over(&self, threshold: Calories) -> Vec<Calories> {
+        return self.items.iter().filter(|&&item| item > threshold).copied().collect();
+    }
+}
+
+/// Elves with more calories come first. Elves with the same calories are
+/// ordered by number, the lowest number first, so rankings never depend on
+/// the order the heap happens to return them in.
+impl Ord for Elf {
+    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
+        return self.calories.cmp(&other.calories).then_with(|| other.number.cmp(&self.number));
+    }
+}
+
+impl PartialOrd for Elf {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Elf {
+    fn eq(&self, other: &Self) -> bool {
+        (self.number, &self.calories) == (other.number, &other.calories)
+    }
+}
+
+impl Eq for Elf {}
+
+
+impl Elves {
+    /// The `count` elves carrying the most calories, most first.
+    pub fn get_top_elves(&self, count: usize) -> Vec<Elf> {
+        let mut heap = self.elves.iter().cloned().collect::<BinaryHeap<Elf>>();
+        let mut top_elves = Vec::new();
+
+        for _ in 0..count {
+            if let Some(elf) = heap.pop() {
+                top_elves.push(elf);
+            }
+        }
+
+        return top_elves;
+    }
+
+    /// The `count` elves carrying the most calories, ranked in `style`.
+    pub fn get_ranked_elves(&self, count: usize, style: RankingStyle) -> Vec<RankedElf> {
+        return rank_elves(&self.get_top_elves(count), style);
+    }
+
+    /// The elf carrying the single largest item, with that item. The lowest
+    /// numbered elf wins a tie.
+    pub fn get_largest_item(&self) -> Option<(&Elf, Calories)> {
+        return self
+            .elves
+            .iter()
+            .filter_map(|elf| elf.largest_item().map(|item| (elf, item)))
+            .max_by(|(a, a_item), (b, b_item)| a_item.cmp(b_item).then(b.number.cmp(&a.number)));
+    }
+
+    /// Every elf carrying items over `threshold` calories, with those items.
+    pub fn get_items_over(&self, threshold: Calories) -> Vec<(&Elf, Vec<Calories>)> {
+        return self
+            .elves
+            .iter()
+            .map(|elf| (elf, elf.items_over(threshold)))
+            .filter(|(_, items)| !items.is_empty())
+            .collect();
+    }
+
+    /// Suggests up to `limit` item moves that even out the elves' totals.
+    /// Each move takes the item from the heaviest elf that brings it closest
+    /// to the lightest elf, and suggestions stop once no move narrows the gap.
+    pub fn get_rebalance_suggestions(&self, limit: usize) -> Vec<Transfer> {
+        let mut elves = self.elves.clone();
+        let mut transfers = Vec::new();
+
+        while transfers.len() < limit {
+            let heaviest = match elves.iter().enumerate().max_by(|(_, a), (_, b)| a.cmp(b)) {
+                Some((index, _)) => index,
+                None => break,
+            };
+            let lightest = match elves.iter().enumerate().min_by(|(_, a), (_, b)| a.calories.cmp(&b.calories).then(a.number.cmp(&b.number))) {
+                Some((index, _)) => index,
+                None => break,
+            };
+
+            // Moving an item narrows the gap only if it is smaller than the gap;
+            // the best one lands closest to half of it. Neither total can
+            // overflow, as the lighter one ends up below the heavier one.
+            let gap = elves[heaviest].calories - elves[lightest].calories;
+            let best = elves[heaviest]
+                .items
+                .iter()
+                .enumerate()
+                .filter(|&(_, &item)| item > 0 && item < gap)
+                .min_by_key(|&(_, &item)| item.abs_diff(gap - item))
+                .map(|(index, _)| index);
+
+            match best {
+                Some(index) => {
+                    let item = elves[heaviest].items.remove(index);
+                    elves[heaviest].calories -= item;
+                    elves[lightest].items.push(item);
+                    elves[lightest].calories += item;
+                    transfers.push(Transfer { calories: item, from: elves[heaviest].number, to: elves[lightest].number });
+                },
+                None => break,
+            }
+        }
+
+        return transfers;
+    }
+
+    /// Summarises the inventory, with a histogram of `buckets` equally wide
+    /// buckets. `None` when there are no elves.
+    pub fn get_stats(&self, buckets: usize) -> Option<Stats> {
+        let mut calories: Vec<Calories> = self.elves.iter().map(|e| e.calories).collect();
+        calories.sort();
+
+        let count = calories.len();
+        let min = self.elves.iter().min_by(|a, b| a.calories.cmp(&b.calories).then(a.number.cmp(&b.number)))?.clone();
+        let max = self.elves.iter().max()?.clone();
+        let sum: u128 = calories.iter().map(|&c| c as u128).sum();
+
+        let median = match count % 2 {
+            0 => (calories[count / 2 - 1] as f64 + calories[count / 2] as f64) / 2.0,
+            _ => calories[count / 2] as f64,
+        };
+
+        return Some(Stats {
+            count,
+            sum,
+            mean: sum as f64 / count as f64,
+            median,
+            p90: percentile(&calories, 90),
+            p99: percentile(&calories, 99),
+            min,
+            max,
+            histogram: histogram(&calories, buckets.max(1)),
+        });
+    }
+}
+
+/// An item to hand from one elf to another.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Transfer {
+    pub calories: Calories,
+    /// The number of the elf giving the item away.
+    pub from: usize,
+    /// The number of the elf receiving it.
+    pub to: usize,
+}
+
+/// A summary of how calories are spread over an inventory.
+pub struct Stats {
+    pub count: usize,
+    /// Wider than [`Calories`], so the total of every elf always fits.
+    pub sum: u128,
+    pub mean: f64,
+    pub median: f64,
+    pub p90: Calories,
+    pub p99: Calories,
+    /// The elf carrying the fewest calories, the lowest number on a tie.
+    pub min: Elf,
+    /// The elf carrying the most calories, the lowest number on a tie.
+    pub max: Elf,
+    pub histogram: Vec<Bucket>,
+}
+
+/// The number of elves carrying from `start` to `end` calories, inclusive.
+pub struct Bucket {
+    pub start: Calories,
+    pub end: Calories,
+    pub count: usize,
+}
+
+/// The nearest-rank percentile of `sorted`, which must not be empty.
+fn percentile(sorted: &[Calories], percent: usize) -> Calories {
+    let rank = (percent * sorted.len()).div_ceil(100).max(1);
+    return sorted[rank - 1];
+}
+
+/// Buckets are worked out in `u128` so that the widest possible range of
+/// calories cannot overflow. The last bucket ends at the largest value.
+/// There are fewer than `buckets` buckets when the range of calories is too
+/// narrow to fill them all, so that no elf is counted twice.
+fn histogram(sorted: &[Calories], buckets: usize) -> Vec<Bucket> {
+    let (low, high) = (sorted[0] as u128, sorted[sorted.len() - 1] as u128);
+    let width = (high - low + 1).div_ceil(buckets as u128);
+
+    return (0..buckets as u128)
+        .map(|index| low + index * width)
+        .take_while(|&start| start <= high)
+        .map(|start| {
+            let end = (start + width - 1).min(high);
+            let count = sorted.iter().filter(|&&c| (start..=end).contains(&(c as u128))).count();
+            return Bucket { start: start as Calories, end: end as Calories, count };
+        })
+        .collect();
+}
+
+/// How elves carrying the same calories share a rank.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Default)]
+pub enum RankingStyle {
+    /// Tied elves share a rank and the next rank follows on: 1, 2, 2, 3.
+    Dense,
+    /// Tied elves share a rank and the ranks they take up are skipped: 1, 2, 2, 4.
+    Competition,
+    /// Every elf gets its own rank, ties broken by elf number: 1, 2, 3, 4.
+    #[default]
+    Ordinal,
+}
+
+impl FromStr for RankingStyle {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<RankingStyle, String> {
+        match value.trim() {
+            "dense" => Ok(RankingStyle::Dense),
+            "competition" => Ok(RankingStyle::Competition),
+            "ordinal" => Ok(RankingStyle::Ordinal),
+            other => Err(format!("Unknown ranking style {}. Can be dense, competition or ordinal", other)),
+        }
+    }
+}
+
+#[derive(Clone)]
+pub struct RankedElf {
+    pub rank: usize,
+    pub elf: Elf,
+}
+
+/// Ranks `elves`, which must already be ordered with the most calories first.
+pub fn rank_elves(elves: &[Elf], style: RankingStyle) -> Vec<RankedElf> {
+    let mut ranked: Vec<RankedElf> = Vec::new();
+
+    for (index, elf) in elves.iter().cloned().enumerate() {
+        let rank = match ranked.last() {
+            Some(previous) if previous.elf.calories == elf.calories && style != RankingStyle::Ordinal => previous.rank,
+            Some(previous) if style == RankingStyle::Dense => previous.rank + 1,
+            _ => index + 1,
+        };
+        ranked.push(RankedElf { rank, elf });
+    }
+
+    return ranked;
+}
+
+/// How one inventory differs from another, with elves matched by number.
+pub struct Diff {
+    pub changed: Vec<Change>,
+    /// Elves only in the newer inventory.
+    pub added: Vec<Elf>,
+    /// Elves only in the older inventory.
+    pub removed: Vec<Elf>,
+    /// The numbers of the top elves in the older inventory, most first.
+    pub top_before: Vec<usize>,
+    /// The numbers of the top elves in the newer inventory, most first.
+    pub top_after: Vec<usize>,
+}
+
+impl Diff {
+    pub fn is_empty(&self) -> bool {
+        return self.changed.is_empty() && self.added.is_empty() && self.removed.is_empty();
+    }
+
+    pub fn ranking_changed(&self) -> bool {
+        return self.top_before != self.top_after;
+    }
+}
+
+/// An elf whose total is different in the newer inventory.
+#[derive(Debug, PartialEq, Eq)]
+pub struct Change {
+    pub number: usize,
+    pub before: Calories,
+    pub after: Calories,
+}
+
+impl Change {
+    /// How many calories the elf gained, negative when it lost some.
+    pub fn delta(&self) -> i128 {
+        return self.after as i128 - self.before as i128;
+    }
+}
+
+/// Compares `before` with `after`, including whether the `top` elves, in
+/// order, are the same in both.
+pub fn diff_elves(before: &Elves, after: &Elves, top: usize) -> Diff {
+    let old: BTreeMap<usize, &Elf> = before.elves.iter().map(|elf| (elf.number, elf)).collect();
+    let new: BTreeMap<usize, &Elf> = after.elves.iter().map(|elf| (elf.number, elf)).collect();
+
+    let changed = new
+        .iter()
+        .filter_map(|(number, elf)| match old.get(number) {
+            Some(previous) if previous.calories != elf.calories => Some(Change { number: *number, before: previous.calories, after: elf.calories }),
+            _ => None,
+        })
+        .collect();
+    let added = new.iter().filter(|(number, _)| !old.contains_key(number)).map(|(_, &elf)| elf.clone()).collect();
+    let removed = old.iter().filter(|(number, _)| !new.contains_key(number)).map(|(_, &elf)| elf.clone()).collect();
+
+    let numbers = |elves: &Elves| elves.get_top_elves(top).iter().map(|elf| elf.number).collect::<Vec<usize>>();
+
+    return Diff { changed, added, removed, top_before: numbers(before), top_after: numbers(after) };
+}
+
+#[cfg(test)]
+const SAMPLE: &str = "1000
+2000
+3000
+
+4000
+
+5000
+6000
+
+7000
+8000
+9000
+
+10000";
+
+#[test]
+fn finds_elf_carrying_the_most_calories(){
+    let answer = Puzzle::solve(aoc_core::Part::One, SAMPLE).unwrap();
+    assert_eq!(answer, Answer::from(24000));
+}
+
+#[test]
+fn sums_calories_of_top_three_elves(){
+    let answer = Puzzle::solve(aoc_core::Part::Two, SAMPLE).unwrap();
+    assert_eq!(answer, Answer::from(45000));
+}
+
+#[test]
+fn reports_line_of_unreadable_calorie_count(){
+    let err = parse_input("1000\n\n20x0\n3000", &mut Recovery::strict()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.column, span.text.as_str()), (3, 1, "20x0"));
+}
+
+#[test]
+fn skips_unreadable_calorie_count_when_lenient(){
+    let mut recovery = Recovery::lenient();
+    let elves = parse_input("1000\n\n20x0\n3000", &mut recovery).unwrap();
+
+    assert_eq!(elves.elves.iter().map(|e| e.calories).collect::<Vec<Calories>>(), vec![1000, 3000]);
+    assert_eq!(recovery.skipped()[0].span.as_ref().unwrap().line, 3);
+}
+
+#[test]
+fn matches_recorded_answers(){
+    let input = std::fs::read_to_string(Puzzle::default_input()).unwrap();
+
+    for part in aoc_core::Part::ALL {
+        aoc_core::answers::assert_recorded::<Puzzle>(part, &input);
+    }
+}
+
+#[cfg(test)]
+const TIED: &str = "3000\n\n5000\n\n3000\n\n5000\n\n1000";
+
+#[test]
+fn orders_tied_elves_by_number(){
+    let elves = parse_input(TIED, &mut Recovery::strict()).unwrap();
+    let numbers: Vec<usize> = elves.get_top_elves(4).iter().map(|e| e.number).collect();
+
+    assert_eq!(numbers, vec![2, 4, 1, 3]);
+}
+
+#[test]
+fn ranks_tied_elves_in_each_style(){
+    let elves = parse_input(TIED, &mut Recovery::strict()).unwrap();
+    let ranks = |style| elves.get_ranked_elves(5, style).iter().map(|r| r.rank).collect::<Vec<usize>>();
+
+    assert_eq!(ranks(RankingStyle::Dense), vec![1, 1, 2, 2, 3]);
+    assert_eq!(ranks(RankingStyle::Competition), vec![1, 1, 3, 3, 5]);
+    assert_eq!(ranks(RankingStyle::Ordinal), vec![1, 2, 3, 4, 5]);
+}
+
+#[test]
+fn streams_the_same_top_elves_as_a_full_parse(){
+    let inputs = [SAMPLE, TIED, "", "\n\n", "1000\n", "\n1000", "1000\n\n\n2000\n\n", "1000\r\n2000\r\n\r\n500", "1000\n\n \r\n"];
+
+    for input in inputs {
+        let elves = parse_input(input, &mut Recovery::strict()).unwrap();
+
+        for count in 0..6 {
+            let streamed = stream_top_elves(input.as_bytes(), Path::new("-"), count, &mut Recovery::strict()).unwrap();
+            let expected = elves.get_top_elves(count);
+
+            assert!(streamed == expected, "{:?} differs for the top {}", input, count);
+        }
+    }
+}
+
+#[test]
+fn reports_line_of_unreadable_calorie_count_when_streaming(){
+    let err = stream_top_elves("1000\n\n20x0\n3000".as_bytes(), Path::new("-"), 3, &mut Recovery::strict()).err().unwrap();
+
+    match err {
+        Error::Parse(err) => assert_eq!(err.span.unwrap().line, 3),
+        other => panic!("{}", other),
+    }
+}
+
+#[test]
+fn summarises_sample_inventory(){
+    let elves = parse_input(SAMPLE, &mut Recovery::strict()).unwrap();
+    let stats = elves.get_stats(2).unwrap();
+
+    assert_eq!((stats.count, stats.sum, stats.mean, stats.median), (5, 55000, 11000.0, 10000.0));
+    assert_eq!((stats.p90, stats.p99), (24000, 24000));
+    assert_eq!((stats.min.number, stats.max.number), (2, 4));
+    assert_eq!(stats.histogram.iter().map(|b| (b.start, b.count)).collect::<Vec<(Calories, usize)>>(), vec![(4000, 4), (14001, 1)]);
+    assert_eq!(elves.elves.iter().map(|e| e.items.len()).collect::<Vec<usize>>(), vec![3, 1, 2, 3, 1]);
+}
+
+#[test]
+fn finds_items_in_sample_inventory(){
+    let elves = parse_input(SAMPLE, &mut Recovery::strict()).unwrap();
+    let (elf, item) = elves.get_largest_item().unwrap();
+    let over: Vec<(usize, Vec<Calories>)> = elves.get_items_over(8500).iter().map(|(elf, items)| (elf.number, items.clone())).collect();
+
+    assert_eq!((elf.number, item), (5, 10000));
+    assert_eq!(over, vec![(4, vec![9000]), (5, vec![10000])]);
+}
+
+#[test]
+fn suggests_moves_that_even_out_totals(){
+    let elves = parse_input("1000\n2000\n3000\n\n1000", &mut Recovery::strict()).unwrap();
+
+    assert_eq!(elves.get_rebalance_suggestions(5), vec![Transfer { calories: 2000, from: 1, to: 2 }]);
+}
+
+#[cfg(test)]
+const OVERFLOWING: &str = "1000\n\n18446744073709551615\n1\n2";
+
+#[test]
+fn reports_elf_whose_total_overflows(){
+    let err = parse_input(OVERFLOWING, &mut Recovery::lenient()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.text.as_str()), (4, "1"));
+    assert!(err.message.contains("Elf 2"), "{}", err.message);
+}
+
+#[test]
+fn reports_elf_whose_total_overflows_when_streaming(){
+    let err = stream_top_elves(OVERFLOWING.as_bytes(), Path::new("-"), 3, &mut Recovery::strict()).err().unwrap();
+
+    match err {
+        Error::Parse(err) => assert_eq!(err.span.unwrap().line, 4),
+        other => panic!("{}", other),
+    }
+}
+
+#[test]
+fn reports_top_three_whose_total_overflows(){
+    let input = "18446744073709551615\n\n1\n\n2";
+
+    assert_eq!(Puzzle::solve(aoc_core::Part::One, input).unwrap(), Answer::from(Calories::MAX));
+    assert!(matches!(Puzzle::solve(aoc_core::Part::Two, input), Err(Error::Solve(_))));
+}
+
+#[test]
+fn diffs_inventories_by_elf_number(){
+    let before = parse_input(SAMPLE, &mut Recovery::strict()).unwrap();
+    let after = parse_input("1000\n2000\n3000\n\n4000\n\n5000\n6000\n\n7000\n8000\n1000", &mut Recovery::strict()).unwrap();
+    let diff = diff_elves(&before, &after, 3);
+
+    assert_eq!(diff.changed, vec![Change { number: 4, before: 24000, after: 16000 }]);
+    assert_eq!(diff.changed[0].delta(), -8000);
+    assert!(diff.added.is_empty());
+    assert_eq!(diff.removed.iter().map(|e| e.number).collect::<Vec<usize>>(), vec![5]);
+    assert_eq!((diff.top_before, diff.top_after), (vec![4, 3, 5], vec![4, 3, 1]));
+}
+
+#[test]
+fn finds_no_differences_between_the_same_inventory(){
+    let elves = parse_input(TIED, &mut Recovery::strict()).unwrap();
+    let diff = diff_elves(&elves, &elves, 3);
+
+    assert!(diff.is_empty());
+    assert!(!diff.ranking_changed());
+}
+
+#[test]
+fn histogram_counts_every_elf_once(){
+    let elves = parse_input("5\n\n5\n\n6", &mut Recovery::strict()).unwrap();
+
+    for buckets in 1..6 {
+        let stats = elves.get_stats(buckets).unwrap();
+        assert_eq!(stats.histogram.iter().map(|b| b.count).sum::<usize>(), stats.count, "{} buckets", buckets);
+    }
+    assert_eq!(elves.get_stats(4).unwrap().histogram.len(), 2);
+}
+
+#[test]
+fn ignores_blank_lines_at_the_end_of_the_input(){
+    let elves = parse_input("1000\n2000\n\n3000\n\n3000\n\n500\n", &mut Recovery::strict()).unwrap();
+    let stats = elves.get_stats(10).unwrap();
+
+    assert_eq!((stats.count, stats.min.number, stats.min.calories), (4, 4, 500));
+    assert_eq!(parse_input("1000\n\n \r\n", &mut Recovery::strict()).unwrap().elves.len(), 1);
+    assert!(parse_input("\n\n", &mut Recovery::strict()).unwrap().elves.is_empty());
+}
+
+#[test]
+fn only_suggests_moves_between_elves_in_the_input(){
+    let elves = parse_input("1000\n2000\n\n3000\n\n3000\n\n500\n", &mut Recovery::strict()).unwrap();
+    let transfers = elves.get_rebalance_suggestions(5);
+
+    assert!(!transfers.is_empty());
+    assert!(transfers.iter().all(|t| (1..=4).contains(&t.from) && (1..=4).contains(&t.to)), "{:?}", transfers);
+    assert_eq!(transfers[0], Transfer { calories: 1000, from: 1, to: 4 });
+}
@@ -1,103 +1,203 @@
-use std::cmp::Ordering;
 use std::path::PathBuf;
+
+use aoc_core::cli::{print_part, print_report, FormatArgs, InputArgs, ParseArgs, PartArgs};
+use aoc_core::input::read_input;
+use aoc_core::recovery::skipped_report;
+use aoc_core::Recovery;
 use clap::Parser;
-use std::fs::{ read_to_string };
-use std::collections::BinaryHeap;
+use day_1::{diff_elves, rank_elves, stream_top_elves, total_calories, Calories, Diff, Elves, Puzzle, RankedElf, RankingStyle, Stats};
 
 fn main() {
     let args = Cli::parse();
 
-    let content = read_to_string(&args.path);
-    
-    match content {
+    if args.stream {
+        return stream(&args);
+    }
+
+    match args.input.read() {
         Ok(value) => {
-            let mut elves: Elves = parse_input(value);
+            if let Some(format) = args.format.format {
+                return print_report::<Puzzle>(&value, &args.part.parts(), args.parse.mode(), format);
+            }
 
-            for elf in &elves.elves {
-                println!("Elf {} has {} calories", elf.number, elf.calories );
+            let elves: Elves = match args.parse.parse_input::<Puzzle>(&value) {
+                Ok(elves) => elves,
+                Err(err) => return println!("{}", err.render(&value)),
+            };
+
+            if let Some(part) = args.part.part {
+                return print_part::<Puzzle>(part, &elves);
+            }
+
+            if let Some(path) = &args.diff {
+                let newer = match read_input(path) {
+                    Ok(newer) => newer,
+                    Err(err) => return println!("{}", err),
+                };
+                return match args.parse.parse_input::<Puzzle>(&newer) {
+                    Ok(newer_elves) => print_diff(&diff_elves(&elves, &newer_elves, args.top), args.top),
+                    Err(err) => println!("{}", err.render(&newer)),
+                };
+            }
+
+            if let Some(threshold) = args.items_over {
+                for (elf, items) in elves.get_items_over(threshold) {
+                    let items: Vec<String> = items.iter().map(|item| item.to_string()).collect();
+                    println!("Elf {} carries items over {} calories: {}", elf.number, threshold, items.join(", "));
+                }
+                return;
             }
 
-            let top_three_elves = elves.get_top_three_elves();
-            let ranked_combined_calories: i32 = top_three_elves.iter().map(|e| e.calories).sum();
+            if let Some(limit) = args.rebalance {
+                let transfers = elves.get_rebalance_suggestions(limit);
+                if transfers.is_empty() {
+                    println!("No item can be moved to even out the elves");
+                }
+                for transfer in transfers {
+                    println!("Move the {} calorie item from elf {} to elf {}", transfer.calories, transfer.from, transfer.to);
+                }
+                return;
+            }
 
-            for (index, ranked_elf) in top_three_elves.iter().enumerate() {
-                println!("Ask elf # {} who ranked {} as he has {} calories worth of food", ranked_elf.number, index + 1, ranked_elf.calories);
+            if args.stats {
+                return match elves.get_stats(args.buckets) {
+                    Some(stats) => print_stats(&elves, &stats),
+                    None => println!("The inventory does not contain any elves"),
+                };
             }
 
-            println!("The combined total calories for the top 3 elves is {}", ranked_combined_calories);
+            for elf in &elves.elves {
+                println!("Elf {} has {} calories", elf.number, elf.calories );
+            }
 
+            print_ranking(&elves.get_ranked_elves(args.top, args.ranking), args.top);
         }
         Err(err) => {
-            println!("Could not read file: {}", err)
+            println!("{}", err)
         }
     }
 }
 
-fn parse_input(value: String) -> Elves {
-    let lines: Vec<&str> = value.split("\n").collect();
-    let segments = lines.split(|f| f.trim().is_empty());
-    let elves = segments.into_iter().enumerate().map(|(index, items)| Elf { number: index + 1, calories: convert_calories(&items) });
-    return Elves { elves: elves.collect() };
-}
-
-fn convert_calories(items: &[&str]) -> i32
-{
-    return items
-        .into_iter()
-        .filter_map(|item| item.parse::<i32>().ok())
-        .sum()
-}
+/// Ranks the input without holding it in memory, for inventories too big to
+/// read in one go.
+fn stream(args: &Cli) {
+    let mut recovery = Recovery::new(args.parse.mode());
+    let top_elves = args.input.open().and_then(|reader| stream_top_elves(reader, &args.input.path, args.top, &mut recovery));
 
-struct Elves {
-    elves: Vec<Elf>
-}
+    if let Some(report) = skipped_report(recovery.skipped()) {
+        eprintln!("{}", report);
+    }
 
-#[derive(Clone, Copy)]
-struct Elf {
-    number: usize,
-    calories: i32,
+    match top_elves {
+        Ok(top_elves) => print_ranking(&rank_elves(&top_elves, args.ranking), args.top),
+        Err(err) => println!("{}", err),
+    }
 }
 
-impl Ord for Elf {
-    fn cmp(&self, other: &Self) -> std::cmp::Ordering {
-        return self.calories.cmp(&other.calories);
+fn print_stats(elves: &Elves, stats: &Stats) {
+    const BAR_WIDTH: usize = 40;
+
+    println!("Elves:    {}", stats.count);
+    println!("Total:    {}", stats.sum);
+    println!("Mean:     {:.1}", stats.mean);
+    println!("Median:   {:.1}", stats.median);
+    println!("p90:      {}", stats.p90);
+    println!("p99:      {}", stats.p99);
+    println!("Min:      {} calories carried by elf {}", stats.min.calories, stats.min.number);
+    println!("Max:      {} calories carried by elf {}", stats.max.calories, stats.max.number);
+    if let Some((elf, item)) = elves.get_largest_item() {
+        println!("Largest item: {} calories carried by elf {}", item, elf.number);
     }
-}
 
-impl PartialOrd for Elf {
-    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
-        Some(self.cmp(other))
+    println!();
+    let tallest = stats.histogram.iter().map(|b| b.count).max().unwrap_or(0).max(1);
+    for bucket in &stats.histogram {
+        let bar = "#".repeat(bucket.count * BAR_WIDTH / tallest);
+        println!("{:>8} - {:>8} | {:<width$} {}", bucket.start, bucket.end, bar, bucket.count, width = BAR_WIDTH);
     }
-}
 
-impl PartialEq for Elf {
-    fn eq(&self, other: &Self) -> bool {
-        (self.number, &self.calories) == (other.number, &other.calories)
+    println!();
+    for elf in &elves.elves {
+        println!("Elf {} carries {} items", elf.number, elf.items.len());
     }
 }
 
-impl Eq for Elf {}
-
+fn print_diff(diff: &Diff, top: usize) {
+    if diff.is_empty() {
+        println!("No elf's calories changed");
+    }
+    for change in &diff.changed {
+        println!("Elf {} changed from {} to {} calories ({:+})", change.number, change.before, change.after, change.delta());
+    }
+    for elf in &diff.added {
+        println!("Elf {} was added with {} calories", elf.number, elf.calories);
+    }
+    for elf in &diff.removed {
+        println!("Elf {} was removed, it had {} calories", elf.number, elf.calories);
+    }
 
-impl Elves {
-    fn get_top_three_elves(&mut self) -> Vec<Elf> {
-        let mut heap = self.elves.iter().copied().collect::<BinaryHeap<Elf>>();
-        let mut top_three = Vec::new();
+    let numbers = |numbers: &[usize]| numbers.iter().map(|n| format!("#{}", n)).collect::<Vec<String>>().join(", ");
+    match diff.ranking_changed() {
+        true => println!("The top {} elves changed from {} to {}", top, numbers(&diff.top_before), numbers(&diff.top_after)),
+        false => println!("The top {} elves are unchanged: {}", top, numbers(&diff.top_after)),
+    }
+}
 
-        for _ in 0..3 {
-            match heap.pop() {
-                Some(elf) => {
-                    top_three.push(elf);
-                }
-                None => {}
-            }
-        }
+fn print_ranking(ranked_elves: &[RankedElf], top: usize) {
+    for ranked_elf in ranked_elves {
+        println!("Ask elf # {} who ranked {} as he has {} calories worth of food", ranked_elf.elf.number, ranked_elf.rank, ranked_elf.elf.calories);
+    }
 
-        return top_three;
+    match total_calories(ranked_elves.iter().map(|r| r.elf.calories)) {
+        Some(combined_calories) => println!("The combined total calories for the top {} elves is {}", top, combined_calories),
+        None => println!("The combined total calories for the top {} elves is more than {}", top, Calories::MAX),
     }
 }
 
 #[derive(Parser)]
 struct Cli {
-    path: PathBuf,
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    part: PartArgs,
+
+    #[command(flatten)]
+    parse: ParseArgs,
+
+    #[command(flatten)]
+    format: FormatArgs,
+
+    /// How many of the best supplied elves to list
+    #[arg(long, default_value_t = 3)]
+    top: usize,
+
+    /// How elves carrying the same calories are ranked: dense, competition or ordinal
+    #[arg(long, default_value = "ordinal")]
+    ranking: RankingStyle,
+
+    /// Read the input line by line and keep only the top elves in memory.
+    /// Only the ranking is printed
+    #[arg(long, conflicts_with_all = ["part", "format"])]
+    stream: bool,
+
+    /// Print statistics about the inventory instead of the ranking
+    #[arg(long, conflicts_with_all = ["part", "format", "stream"])]
+    stats: bool,
+
+    /// Compare the input with a newer inventory, matching elves by number
+    #[arg(long, value_name = "NEWER", conflicts_with_all = ["part", "format", "stream", "stats"])]
+    diff: Option<PathBuf>,
+
+    /// List the items carrying more than this many calories
+    #[arg(long, value_name = "CALORIES", conflicts_with_all = ["part", "format", "stream", "stats", "diff"])]
+    items_over: Option<Calories>,
+
+    /// Suggest up to this many item moves that even out the elves' totals
+    #[arg(long, value_name = "MOVES", conflicts_with_all = ["part", "format", "stream", "stats", "diff", "items_over"])]
+    rebalance: Option<usize>,
+
+    /// How many buckets the --stats histogram has
+    #[arg(long, default_value_t = 10)]
+    buckets: usize,
 }
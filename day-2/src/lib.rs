@@ -0,0 +1,336 @@
+use std::path::{Path, PathBuf};
+use std::str::FromStr;
+use aoc_core::{Answer, Error, ParseError, Recovery, Solution};
+
+pub mod replay;
+pub mod rules;
+pub mod strategy;
+pub mod tournament;
+
+use rules::{cyclic_counter, cyclic_outcome};
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Round>;
+
+    const DAY: u8 = 2;
+
+    fn default_input() -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join("day2.input");
+    }
+
+    fn parse_with(input: &str, recovery: &mut Recovery) -> Result<Vec<Round>, ParseError> {
+        return parse_rounds(input, recovery);
+    }
+
+    fn part1(rounds: &Vec<Round>) -> Result<Answer, Error> {
+        return Ok(score_rounds(rounds, GuideInterpretation::Shape).into());
+    }
+
+    fn part2(rounds: &Vec<Round>) -> Result<Answer, Error> {
+        return Ok(score_rounds(rounds, GuideInterpretation::Outcome).into());
+    }
+}
+
+pub fn calculate_total_score(games: &[Game]) -> i32 {
+    return games.iter().map(|game| game.get_total_score()).sum()
+}
+
+/// Scores `rounds` with their second column read as `interpretation`.
+pub fn score_rounds(rounds: &[Round], interpretation: GuideInterpretation) -> i32 {
+    return rounds.iter().map(|round| round.to_game(interpretation).get_total_score()).sum();
+}
+
+/// The lines of the strategy guide that hold a round, with their line numbers
+/// counting from 1. Blank lines are skipped.
+pub fn round_lines(value: &str) -> impl Iterator<Item = (usize, &str)> {
+    return value.split("\n").enumerate().filter(|(_, round_line)| !round_line.trim().is_empty()).map(|(index, round_line)| (index + 1, round_line));
+}
+
+/// How many lines of the strategy guide hold a round, whether or not they parse.
+pub fn count_round_lines(value: &str) -> usize {
+    return round_lines(value).count();
+}
+
+pub fn parse_rounds(value: &str, recovery: &mut Recovery) -> Result<Vec<Round>, ParseError> {
+    return round_lines(value)
+        .map(|(line, round_line)| recovery.recover(Round::parse(value, round_line, line)))
+        .filter_map(|round| round.transpose())
+        .collect();
+}
+
+/// Reads the strategy guide with its second column read as `interpretation`.
+pub fn parse_games(value: &str, interpretation: GuideInterpretation, recovery: &mut Recovery) -> Result<Vec<Game>, ParseError> {
+    return Ok(parse_rounds(value, recovery)?.iter().map(|round| round.to_game(interpretation)).collect());
+}
+
+/// What the second column of the strategy guide means.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum GuideInterpretation {
+    /// X, Y and Z are the shape to play, the part 1 reading.
+    Shape,
+    /// X, Y and Z are the outcome to aim for, the part 2 reading.
+    Outcome,
+}
+
+impl GuideInterpretation {
+    pub const ALL: [GuideInterpretation; 2] = [GuideInterpretation::Shape, GuideInterpretation::Outcome];
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            GuideInterpretation::Shape => "the shape to play",
+            GuideInterpretation::Outcome => "the outcome to aim for",
+        }
+    }
+}
+
+impl FromStr for GuideInterpretation {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<GuideInterpretation, String> {
+        match value.trim() {
+            "shape" => Ok(GuideInterpretation::Shape),
+            "outcome" => Ok(GuideInterpretation::Outcome),
+            other => Err(format!("Unknown interpretation {}. Can be shape or outcome", other)),
+        }
+    }
+}
+
+/// A single line of the strategy guide, before deciding what its second column means.
+#[derive(Clone)]
+pub struct Round {
+    line: usize,
+    opponent_move: Shape,
+    response: Response,
+}
+
+impl Round {
+    /// Parses `round_line`, which must be line `line` of `source`, so that
+    /// errors can point at the bad token.
+    fn parse(source: &str, round_line: &str, line: usize) -> Result<Round, ParseError> {
+        let columns: Vec<&str> = round_line.split_whitespace().collect();
+        match columns[..] {
+            [opponent_move_raw, response_raw] => {
+                let opponent_move = Shape::parse(opponent_move_raw)
+                    .ok_or_else(|| ParseError::spanning(source, opponent_move_raw, format!("Unknown opponent move {}, expected A, B or C", opponent_move_raw)))?;
+                let response = Response::parse(response_raw)
+                    .ok_or_else(|| ParseError::spanning(source, response_raw, format!("Unknown response {}, expected X, Y or Z", response_raw)))?;
+
+                return Ok(Round { line, opponent_move, response });
+            },
+            _ => Err(ParseError::spanning(source, round_line.trim(), "Expected an opponent move and a response separated by a space"))
+        }
+    }
+
+    /// The line of the strategy guide the round was read from.
+    pub fn line(&self) -> usize {
+        return self.line;
+    }
+
+    pub fn to_game(&self, interpretation: GuideInterpretation) -> Game {
+        match interpretation {
+            GuideInterpretation::Shape => self.to_game_by_shape(),
+            GuideInterpretation::Outcome => self.to_game_by_outcome(),
+        }
+    }
+
+    /// Part 1 reading: X, Y and Z are the shape to play.
+    pub fn to_game_by_shape(&self) -> Game {
+        return Game { opponent_move: self.opponent_move, counter_move: self.response.to_shape() };
+    }
+
+    /// Part 2 reading: X, Y and Z are the outcome to aim for.
+    pub fn to_game_by_outcome(&self) -> Game {
+        let counter_move = Shape::create_for_outcome(&self.response.to_outcome(), &self.opponent_move);
+        return Game { opponent_move: self.opponent_move, counter_move };
+    }
+}
+
+#[derive(Clone, Copy)]
+pub enum Response {
+    X,
+    Y,
+    Z,
+}
+
+impl Response {
+    fn parse(value: &str) -> Option<Response> {
+        match value.to_uppercase().as_str() {
+            "X" => return Some(Response::X),
+            "Y" => return Some(Response::Y),
+            "Z" => return Some(Response::Z),
+            _ => return None
+        }
+    }
+
+    fn to_shape(self) -> Shape {
+        match self {
+            Response::X => Shape::Rock,
+            Response::Y => Shape::Paper,
+            Response::Z => Shape::Scissors,
+        }
+    }
+
+    fn to_outcome(self) -> Outcome {
+        match self {
+            Response::X => Outcome::Loss,
+            Response::Y => Outcome::Draw,
+            Response::Z => Outcome::Win,
+        }
+    }
+}
+
+pub struct Game {
+    opponent_move: Shape,
+    counter_move:  Shape,
+}
+
+impl Game {
+    fn get_outcome(&self) -> Outcome{
+        return cyclic_outcome(Shape::ALL.len(), self.opponent_move.index(), self.counter_move.index());
+    }
+
+    fn get_total_score(&self) -> i32 {
+        let outcome = self.get_outcome_score();
+        let shape_score = self.counter_move.get_score();
+        return outcome + shape_score;
+    }
+
+    fn get_outcome_score(&self) -> i32 {
+        match self.get_outcome() {
+            Outcome::Win => return 6,
+            Outcome::Draw => return 3,
+            Outcome::Loss => return 0,
+        }
+    }
+
+}
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Outcome {
+    Win,
+    Draw,
+    Loss,
+}
+
+/// Listed in cycle order, each shape beating the one before it.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Shape {
+    Rock,
+    Paper,
+    Scissors,
+}
+
+impl Shape {
+    const ALL: [Shape; 3] = [Shape::Rock, Shape::Paper, Shape::Scissors];
+
+    fn index(self) -> usize {
+        return self as usize;
+    }
+
+    fn parse(value: &str) -> Option<Shape> {
+        match value.to_uppercase().as_str() {
+            "A" => return Some(Shape::Rock),
+            "B" => return Some(Shape::Paper),
+            "C" => return Some(Shape::Scissors),
+            _ => return None
+        }
+    }
+
+    fn create_for_outcome(outcome: &Outcome, opponent_shape: &Shape) -> Shape {
+        return Shape::ALL[cyclic_counter(Shape::ALL.len(), *outcome, opponent_shape.index())];
+    }
+
+    fn get_score(&self) -> i32 {
+        match self {
+            Shape::Rock => 1,
+            Shape::Paper => 2,
+            Shape::Scissors => 3,
+        }
+    }
+}
+
+
+#[test]
+fn calculates_sample_score(){
+    let sample = "A Y \n
+    B X \n
+    C Z";
+
+    let games: Vec<Game> = parse_games(sample, GuideInterpretation::Outcome, &mut Recovery::strict()).unwrap();
+    let total_score = calculate_total_score(&games);
+
+    assert_eq!(total_score, 12)
+}
+
+#[test]
+fn calculates_sample_score_reading_responses_as_shapes(){
+    let sample = "A Y \n
+    B X \n
+    C Z";
+
+    let answer = Puzzle::solve(aoc_core::Part::One, sample).unwrap();
+
+    assert_eq!(answer, Answer::from(15))
+}
+
+#[test]
+fn reports_unknown_response_with_its_position(){
+    let err = parse_rounds("A Y\nB Q\nC Z", &mut Recovery::strict()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.column, span.text.as_str()), (2, 3, "Q"));
+}
+
+#[test]
+fn reports_unknown_opponent_move_with_its_position(){
+    let err = parse_rounds("A Y\nB X\nD Y", &mut Recovery::strict()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.column, span.text.as_str()), (3, 1, "D"));
+}
+
+#[test]
+fn reports_round_without_a_response(){
+    let err = parse_rounds("A Y\n  B  \nC Z", &mut Recovery::strict()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.column, span.text.as_str()), (2, 3, "B"));
+}
+
+#[test]
+fn counts_rounds_skipped_when_lenient(){
+    let input = "A Y\nA Q\n\nD Y\nC Z\n";
+    let mut recovery = Recovery::lenient();
+    let rounds = parse_rounds(input, &mut recovery).unwrap();
+
+    assert_eq!((rounds.len(), count_round_lines(input)), (2, 4));
+    assert_eq!(recovery.skipped().iter().map(|err| err.span.as_ref().unwrap().line).collect::<Vec<usize>>(), vec![2, 4]);
+}
+
+#[test]
+fn skips_unknown_response_when_lenient(){
+    let mut recovery = Recovery::lenient();
+    let games = parse_games("A Y\nB Q\nC Z", GuideInterpretation::Outcome, &mut recovery).unwrap();
+
+    assert_eq!(calculate_total_score(&games), 11);
+    assert_eq!(recovery.skipped().len(), 1);
+}
+
+#[test]
+fn matches_recorded_answers(){
+    let input = std::fs::read_to_string(Puzzle::default_input()).unwrap();
+
+    for part in aoc_core::Part::ALL {
+        aoc_core::answers::assert_recorded::<Puzzle>(part, &input);
+    }
+}
+
+#[test]
+fn scores_both_interpretations_from_the_same_rounds(){
+    let rounds = parse_rounds("A Y\nB X\nC Z", &mut Recovery::strict()).unwrap();
+    let scores: Vec<i32> = GuideInterpretation::ALL.iter().map(|&interpretation| score_rounds(&rounds, interpretation)).collect();
+
+    assert_eq!(scores, vec![15, 12]);
+}
@@ -1,172 +1,303 @@
-use std::path::PathBuf;
+use std::path::{Path, PathBuf};
+
+use aoc_core::cli::{print_part, print_run, FormatArgs, InputArgs, ParseArgs, PartArgs};
+use aoc_core::input::read_input;
+use aoc_core::recovery::skipped_report;
+use aoc_core::run::run;
+use aoc_core::{ParseError, Recovery};
 use clap::Parser;
-use std::fs::{ read_to_string };
+use day_2::replay::{replay, Replay};
+use day_2::rules::{Play, Rules};
+use day_2::strategy::{solve_strategy, OpponentModel};
+use day_2::tournament::{round_robin, Entrant, Standing};
+use day_2::{count_round_lines, score_rounds, GuideInterpretation, Puzzle, Round};
 
 fn main() {
     let args = Cli::parse();
-    let content = read_to_string(&args.path);
 
-    match content {
+    match args.input.read() {
         Ok(value) => {
-            let games: Vec<Game> = parse_games(&value);
-            let total_score = calculate_total_score(games);
+            if let Some(format) = args.format.format {
+                let run = run::<Puzzle>(&value, &args.part.parts(), args.parse.mode());
+                print_run(&value, &run, format);
+                if run.parse.is_ok() {
+                    warn_unscored(count_round_lines(&value) - run.skipped.len(), &value, "the guide");
+                }
+                return;
+            }
+
+            if args.rules.is_some() || args.optimal.is_some() {
+                let rules = match load_rules(args.rules.as_deref()) {
+                    Ok(rules) => rules,
+                    Err(message) => return println!("{}", message),
+                };
+
+                let readings = match parse_plays(&args, &rules, &value) {
+                    Some(readings) => readings,
+                    None => return,
+                };
+
+                return match args.optimal {
+                    Some(model) => print_strategy(&rules, model, &readings),
+                    None => play_with_rules(&rules, &readings),
+                };
+            }
+
+            let rounds: Vec<Round> = match args.parse.parse_input::<Puzzle>(&value) {
+                Ok(rounds) => rounds,
+                Err(err) => return println!("{}", err.render(&value)),
+            };
 
-            println!("The total score will be {}", total_score);
+            warn_unscored(rounds.len(), &value, "the guide");
 
+            if !args.tournament.is_empty() {
+                return run_tournament(&args, &rounds);
+            }
+
+            if args.replay {
+                for interpretation in args.interpretations() {
+                    println!("Reading X/Y/Z as {}:", interpretation.describe());
+                    print_replay(&replay(&rounds, interpretation));
+                }
+                return;
+            }
+
+            match args.part.part {
+                Some(part) => print_part::<Puzzle>(part, &rounds),
+                None => {
+                    for interpretation in args.interpretations() {
+                        let total_score = score_rounds(&rounds, interpretation);
+                        println!("Reading X/Y/Z as {}, the total score will be {}", interpretation.describe(), total_score);
+                    }
+                }
+            }
         }
         Err(err) => {
-            println!("Could not read file: {}", err)
+            println!("{}", err)
         }
     }
 }
 
-fn calculate_total_score(games: Vec<Game>) -> i32 {
-    return games.iter().map(|game| game.get_total_score()).sum()
+/// Says how many of the rounds in `guide` are left when a lenient parse
+/// skipped some of them.
+fn warn_unscored(scored: usize, value: &str, guide: &str) {
+    let round_lines = count_round_lines(value);
+    if scored < round_lines {
+        eprintln!("Only {} of the {} rounds in {} are scored\n", scored, round_lines, guide);
+    }
 }
 
-fn parse_games(value: &str) -> Vec<Game> {
-    return value
-        .split("\n")
-        .filter_map(|game_line| {
-            let moves: Vec<&str> = game_line.split_whitespace().collect();
-            match moves[..] {
-                [opponent_move_raw, outcome_raw] => {
-                    let game = (Shape::parse(opponent_move_raw), Outcome::parse(outcome_raw));
-
-                    match game {
-                        (Some(opponent_move),  Some(expected_outcome)) => {
-                            let counter_move = Shape::create_for_outcome(&expected_outcome, &opponent_move);
-                            Some(Game{ opponent_move: opponent_move, counter_move: counter_move  })
-                        },
-                        _ => {
-                            return None;
-                        }
-                    }
-                },
-                _ => None
-            }
-        }).collect();
-}
+fn print_replay(replay: &Replay) {
+    println!("{:>5}  {:<9} {:<9} {:<8} {:>5} {:>7} {:>7}", "Line", "Opponent", "Us", "Outcome", "Shape", "Outcome", "Total");
+    for round in &replay.rounds {
+        println!(
+            "{:>5}  {:<9} {:<9} {:<8} {:>5} {:>7} {:>7}",
+            round.line,
+            format!("{:?}", round.opponent_move),
+            format!("{:?}", round.counter_move),
+            format!("{:?}", round.outcome),
+            round.shape_score,
+            round.outcome_score,
+            round.total
+        );
+    }
 
-struct Game {
-    opponent_move: Shape,
-    counter_move:  Shape,
+    println!("Wins: {}, draws: {}, losses: {}", replay.wins, replay.draws, replay.losses);
+    println!("Longest streaks: {} wins, {} draws, {} losses", replay.longest_win_streak, replay.longest_draw_streak, replay.longest_loss_streak);
+    println!("The total score will be {}", replay.total());
+    println!();
 }
 
-impl Game {
-    fn get_outcome(&self) -> Outcome{
-        match self {
-            Game { opponent_move: Shape::Rock, counter_move: Shape::Paper } => Outcome::Win,
-            Game { opponent_move: Shape::Rock, counter_move: Shape::Rock } => Outcome::Draw,
-            Game { opponent_move: Shape::Rock, counter_move: Shape::Scissors } => Outcome::Loss,
-            Game { opponent_move: Shape::Paper, counter_move: Shape::Scissors } => Outcome::Win,
-            Game { opponent_move: Shape::Paper, counter_move: Shape::Paper } => Outcome::Draw,
-            Game { opponent_move: Shape::Paper, counter_move: Shape::Rock } => Outcome::Loss,
-            Game { opponent_move: Shape::Scissors, counter_move: Shape::Rock } => Outcome::Win,
-            Game { opponent_move: Shape::Scissors, counter_move: Shape::Scissors } => Outcome::Draw,
-            Game { opponent_move: Shape::Scissors, counter_move: Shape::Paper } => Outcome::Loss,
+/// Plays the input guide, every --tournament guide and the random baselines
+/// against each other.
+fn run_tournament(args: &Cli, rounds: &[Round]) {
+    let mut guides = vec![(guide_name(&args.input.path), rounds.to_vec())];
+    for path in &args.tournament {
+        let value = match read_input(path) {
+            Ok(value) => value,
+            Err(err) => return println!("{}", err),
+        };
+        match args.parse.parse_input::<Puzzle>(&value) {
+            Ok(rounds) => {
+                warn_unscored(rounds.len(), &value, &guide_name(path));
+                guides.push((guide_name(path), rounds));
+            }
+            Err(err) => return println!("{}", err.render(&value)),
         }
     }
 
-    fn get_total_score(&self) -> i32 {
-        let outcome = self.get_outcome_score();
-        let shape_score = self.counter_move.get_score();
-        return outcome + shape_score;
-    }
+    let last_line = guides.iter().filter_map(|(_, rounds)| rounds.last().map(|round| round.line())).max().unwrap_or(0);
 
-    fn get_outcome_score(&self) -> i32 {
-        match self.get_outcome() {
-            Outcome::Win => return 6,
-            Outcome::Draw => return 3,
-            Outcome::Loss => return 0,
+    for interpretation in args.interpretations() {
+        let mut entrants: Vec<Entrant> = guides.iter().map(|(name, rounds)| Entrant::from_rounds(name.clone(), rounds, interpretation)).collect();
+        for index in 0..args.random {
+            let seed = args.seed.wrapping_add(index as u64);
+            entrants.push(Entrant::random(format!("random (seed {})", seed), last_line, seed));
         }
+
+        println!("Reading X/Y/Z as {}:", interpretation.describe());
+        print_league(&round_robin(&entrants));
+        println!();
     }
+}
 
+fn guide_name(path: &Path) -> String {
+    return path.file_name().map(|name| name.to_string_lossy().to_string()).unwrap_or_else(|| path.display().to_string());
 }
 
-enum Outcome {
-    Win,
-    Draw,
-    Loss,
+fn print_league(table: &[Standing]) {
+    let width = table.iter().map(|standing| standing.name.len()).max().unwrap_or(0).max("Guide".len());
+
+    println!("{:<width$}  {:>6} {:>4} {:>5} {:>6} {:>6} {:>8} {:>8}", "Guide", "Played", "Won", "Drawn", "Lost", "Points", "For", "Against", width = width);
+    for standing in table {
+        println!(
+            "{:<width$}  {:>6} {:>4} {:>5} {:>6} {:>6} {:>8} {:>8}",
+            standing.name, standing.played, standing.wins, standing.draws, standing.losses, standing.points, standing.score_for, standing.score_against,
+            width = width
+        );
+    }
 }
 
-impl Outcome {
-    fn parse(value: &str) -> Option<Outcome> {
-        match value {
-            "X" => return Some(Outcome::Loss),
-            "Y" => return Some(Outcome::Draw),
-            "Z" => return Some(Outcome::Win),
-            _ => None
+/// The rules read from `path`, or Rock-Paper-Scissors when there is none.
+fn load_rules(path: Option<&Path>) -> Result<Rules, String> {
+    match path {
+        Some(path) => {
+            let content = read_input(path).map_err(|err| err.to_string())?;
+            return Rules::from_toml(&content).map_err(|message| format!("Could not use rules {}: {}", path.display(), message));
         }
+        None => Ok(Rules::classic()),
     }
 }
 
-enum Shape {
-    Rock,
-    Paper,
-    Scissors,
-}
+/// Reads the guide in `value` with `rules` once for every interpretation.
+/// Skipped lines and unscored rounds are reported once for the whole guide
+/// rather than once per reading. `None` when it could not be parsed.
+fn parse_plays(args: &Cli, rules: &Rules, value: &str) -> Option<Vec<(GuideInterpretation, Vec<Play>)>> {
+    let mut readings = Vec::new();
+    let mut skipped: Vec<ParseError> = Vec::new();
 
-impl Shape {
-    fn parse(value: &str) -> Option<Shape> {
-        match value.to_uppercase().as_str() {
-            "A" => return Some(Shape::Rock),
-            "B" => return Some(Shape::Paper),
-            "C" => return Some(Shape::Scissors),
-            _ => return None
+    for interpretation in args.interpretations() {
+        let mut recovery = Recovery::new(args.parse.mode());
+        match rules.parse_plays(value, interpretation, &mut recovery) {
+            Ok(plays) => readings.push((interpretation, plays)),
+            Err(err) => {
+                println!("{}", err.render(value));
+                return None;
+            }
         }
-    }
 
-    fn create_for_outcome(outcome: &Outcome, opponent_shape: &Shape) -> Shape {
-        match outcome {
-            Outcome::Win => {
-                match opponent_shape {
-                    Shape::Paper => Shape::Scissors,
-                    Shape::Rock => Shape::Paper,
-                    Shape::Scissors => Shape::Rock,
-                }
-            },
-            Outcome::Draw => {
-                match opponent_shape {
-                    Shape::Paper => Shape::Paper,
-                    Shape::Scissors => Shape::Scissors,
-                    Shape:: Rock => Shape::Rock,
-                }
-            },
-            Outcome::Loss => {
-                match opponent_shape {
-                    Shape::Paper => Shape::Rock,
-                    Shape::Rock => Shape::Scissors,
-                    Shape::Scissors => Shape::Paper
-                }
+        // A line more than one reading skips is listed once, with the first reading's error.
+        for err in recovery.into_skipped() {
+            if !skipped.iter().any(|seen| seen.span == err.span) {
+                skipped.push(err);
             }
         }
     }
 
-    fn get_score(&self) -> i32 {
-        match self {
-            Shape::Rock => 1,
-            Shape::Paper => 2,
-            Shape::Scissors => 3,
+    skipped.sort_by_key(|err| err.span.as_ref().map(|span| (span.line, span.column)));
+    if let Some(report) = skipped_report(&skipped) {
+        eprintln!("{}", report);
+    }
+
+    // Readings only differ in what they skip when the response codes differ.
+    let scored: Vec<usize> = readings.iter().map(|(_, plays)| plays.len()).collect();
+    if scored.windows(2).all(|pair| pair[0] == pair[1]) {
+        warn_unscored(scored.first().copied().unwrap_or(0), value, "the guide");
+    } else {
+        let round_lines = count_round_lines(value);
+        for (interpretation, plays) in &readings {
+            eprintln!("Reading the response as {}, only {} of the {} rounds in the guide are scored", interpretation.describe(), plays.len(), round_lines);
         }
+        eprintln!();
     }
+
+    return Some(readings);
 }
 
+fn play_with_rules(rules: &Rules, readings: &[(GuideInterpretation, Vec<Play>)]) {
+    for (interpretation, plays) in readings {
+        println!("Reading the response as {}, the total score will be {}", interpretation.describe(), rules.total_score(plays));
+    }
+}
+
+/// Prints the best counter-moves to the guide's opponent moves and how they
+/// compare with following the guide. Each reading is solved from the rounds
+/// it scores, since a round one reading cannot parse is skipped by it.
+fn print_strategy(rules: &Rules, model: OpponentModel, readings: &[(GuideInterpretation, Vec<Play>)]) {
+    for (interpretation, plays) in readings {
+        let strategy = solve_strategy(rules, plays, model);
+        let total = rules.total_score(plays);
+
+        println!("Reading the response as {}:", interpretation.describe());
+        match model {
+            OpponentModel::Fixed => {
+                println!("The best counters {} are:", model.describe());
+                for (opponent, &counter) in strategy.counters.iter().enumerate() {
+                    println!("  {} against {}", rules.shapes[counter].name, rules.shapes[opponent].name);
+                }
+            }
+            OpponentModel::Distribution => {
+                println!("The best shape to play every round {} is {}", model.describe(), rules.shapes[strategy.counters[0]].name);
+            }
+        }
+        println!("Expected score: {:.2} per round, {:.0} in total", strategy.expected_per_round(), strategy.expected_total);
+        println!("The guide scores {} and this gains {:+.0}", total, strategy.gain_over(total));
+        println!();
+    }
+}
 
 #[derive(Parser)]
 struct Cli {
-    path: PathBuf,
-}
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    part: PartArgs,
+
+    #[command(flatten)]
+    parse: ParseArgs,
+
+    #[command(flatten)]
+    format: FormatArgs,
+
+    /// Read X/Y/Z only as the shape to play or as the outcome to aim for.
+    /// Both readings are scored by default
+    #[arg(long, conflicts_with_all = ["part", "format"])]
+    interpretation: Option<GuideInterpretation>,
 
+    /// Play with the shapes, scores and codes from this TOML file instead of
+    /// Rock-Paper-Scissors, for example day-2/rpsls.toml
+    #[arg(long, value_name = "FILE", conflicts_with_all = ["part", "format"])]
+    rules: Option<PathBuf>,
 
-#[test]
-fn calculates_sample_score(){
-    let sample = "A Y \n
-    B X \n
-    C Z";
+    /// Work out the counter-moves that score the most against the guide's
+    /// opponent moves, either fixed or drawn from their frequencies
+    #[arg(long, value_name = "MODEL", conflicts_with_all = ["part", "format"])]
+    optimal: Option<OpponentModel>,
 
-    let games: Vec<Game> = parse_games(&sample);
-    let total_score = calculate_total_score(games);
+    /// List every round with its shapes, outcome, scores and running total
+    #[arg(long, conflicts_with_all = ["part", "format", "rules", "optimal"])]
+    replay: bool,
 
-    assert_eq!(total_score, 12)
-}
\ No newline at end of file
+    /// Play the input guide against these guides, round-robin, and print a
+    /// league table
+    #[arg(long, value_name = "GUIDE", num_args = 1.., conflicts_with_all = ["part", "format", "rules", "optimal", "replay"])]
+    tournament: Vec<PathBuf>,
+
+    /// How many random guides join the --tournament as baselines
+    #[arg(long, default_value_t = 0, requires = "tournament")]
+    random: usize,
+
+    /// The seed of the first random guide, each one after it adds one
+    #[arg(long, default_value_t = 2022, requires = "tournament")]
+    seed: u64,
+}
+
+impl Cli {
+    fn interpretations(&self) -> Vec<GuideInterpretation> {
+        match self.interpretation {
+            Some(interpretation) => vec![interpretation],
+            None => GuideInterpretation::ALL.to_vec(),
+        }
+    }
+}
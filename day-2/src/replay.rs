@@ -0,0 +1,100 @@
+use crate::{GuideInterpretation, Outcome, Round, Shape};
+
+/// One round of a replay, with the scores that make up the total.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ReplayRound {
+    pub line: usize,
+    pub opponent_move: Shape,
+    pub counter_move: Shape,
+    pub outcome: Outcome,
+    pub shape_score: i32,
+    pub outcome_score: i32,
+    /// The total score up to and including this round.
+    pub total: i32,
+}
+
+/// Every round of a strategy guide played out in order, to explain its total.
+pub struct Replay {
+    pub rounds: Vec<ReplayRound>,
+    pub wins: usize,
+    pub draws: usize,
+    pub losses: usize,
+    /// The most rounds in a row with each outcome.
+    pub longest_win_streak: usize,
+    pub longest_draw_streak: usize,
+    pub longest_loss_streak: usize,
+}
+
+impl Replay {
+    pub fn total(&self) -> i32 {
+        return self.rounds.last().map(|round| round.total).unwrap_or(0);
+    }
+}
+
+/// Plays `rounds` in order with their second column read as `interpretation`.
+pub fn replay(rounds: &[Round], interpretation: GuideInterpretation) -> Replay {
+    let mut replay = Replay { rounds: Vec::new(), wins: 0, draws: 0, losses: 0, longest_win_streak: 0, longest_draw_streak: 0, longest_loss_streak: 0 };
+    let mut streak = 0;
+    let mut total = 0;
+
+    for round in rounds {
+        let game = round.to_game(interpretation);
+        let outcome = game.get_outcome();
+        let shape_score = game.counter_move.get_score();
+        let outcome_score = game.get_outcome_score();
+        total += shape_score + outcome_score;
+
+        streak = match replay.rounds.last() {
+            Some(previous) if previous.outcome == outcome => streak + 1,
+            _ => 1,
+        };
+        let (count, longest) = match outcome {
+            Outcome::Win => (&mut replay.wins, &mut replay.longest_win_streak),
+            Outcome::Draw => (&mut replay.draws, &mut replay.longest_draw_streak),
+            Outcome::Loss => (&mut replay.losses, &mut replay.longest_loss_streak),
+        };
+        *count += 1;
+        *longest = (*longest).max(streak);
+
+        replay.rounds.push(ReplayRound {
+            line: round.line(),
+            opponent_move: game.opponent_move,
+            counter_move: game.counter_move,
+            outcome,
+            shape_score,
+            outcome_score,
+            total,
+        });
+    }
+
+    return replay;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_rounds;
+    use aoc_core::Recovery;
+
+    #[test]
+    fn replays_sample_round_by_round() {
+        let rounds = parse_rounds("A Y\n\nB X\nC Z\n", &mut Recovery::strict()).unwrap();
+        let replay = replay(&rounds, GuideInterpretation::Shape);
+
+        assert_eq!(
+            replay.rounds[1],
+            ReplayRound { line: 3, opponent_move: Shape::Paper, counter_move: Shape::Rock, outcome: Outcome::Loss, shape_score: 1, outcome_score: 0, total: 9 }
+        );
+        assert_eq!(replay.rounds.iter().map(|r| r.total).collect::<Vec<i32>>(), vec![8, 9, 15]);
+        assert_eq!((replay.wins, replay.draws, replay.losses), (1, 1, 1));
+    }
+
+    #[test]
+    fn finds_longest_streaks() {
+        let rounds = parse_rounds("A Y\nB Z\nC X\nA X\nB Y\nC Z\nA Z", &mut Recovery::strict()).unwrap();
+        let replay = replay(&rounds, GuideInterpretation::Shape);
+
+        assert_eq!((replay.longest_win_streak, replay.longest_draw_streak, replay.longest_loss_streak), (3, 3, 1));
+        assert_eq!(replay.total(), crate::score_rounds(&rounds, GuideInterpretation::Shape));
+    }
+}
@@ -0,0 +1,291 @@
+use std::collections::HashSet;
+
+use aoc_core::{ParseError, Recovery};
+use serde::Deserialize;
+
+use crate::{round_lines, GuideInterpretation, Outcome};
+
+/// The outcome for the shape at `counter` played against the shape at
+/// `opponent`, out of `count` shapes in cycle order. Each shape beats the
+/// `(count - 1) / 2` shapes just before it, wrapping around.
+pub fn cyclic_outcome(count: usize, opponent: usize, counter: usize) -> Outcome {
+    let distance = (counter + count - opponent) % count;
+    match distance {
+        0 => Outcome::Draw,
+        _ if distance <= (count - 1) / 2 => Outcome::Win,
+        _ => Outcome::Loss,
+    }
+}
+
+/// The shape to play against the shape at `opponent` for `outcome`, out of
+/// `count` shapes in cycle order. When several shapes would do, it is the
+/// nearest one in the cycle.
+pub fn cyclic_counter(count: usize, outcome: Outcome, opponent: usize) -> usize {
+    match outcome {
+        Outcome::Draw => opponent,
+        Outcome::Win => (opponent + 1) % count,
+        Outcome::Loss => (opponent + count - 1) % count,
+    }
+}
+
+/// The shapes, scores and letter codes of a cyclic hand game, such as
+/// Rock-Paper-Scissors or Rock-Paper-Scissors-Lizard-Spock.
+#[derive(Debug, Deserialize)]
+pub struct Rules {
+    /// In cycle order, see [`cyclic_outcome`].
+    #[serde(rename = "shape")]
+    pub shapes: Vec<ShapeRule>,
+    /// The codes for the outcome to aim for, when the guide is read that way.
+    pub outcomes: OutcomeCodes,
+    #[serde(default)]
+    pub scores: OutcomeScores,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ShapeRule {
+    pub name: String,
+    pub score: i32,
+    /// The code for the opponent playing this shape.
+    pub opponent: String,
+    /// The code for playing this shape, when the guide is read that way.
+    pub response: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutcomeCodes {
+    pub loss: String,
+    pub draw: String,
+    pub win: String,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct OutcomeScores {
+    pub loss: i32,
+    pub draw: i32,
+    pub win: i32,
+}
+
+impl Default for OutcomeScores {
+    fn default() -> OutcomeScores {
+        return OutcomeScores { loss: 0, draw: 3, win: 6 };
+    }
+}
+
+/// One round under a set of [`Rules`], with shapes given by their position in
+/// the cycle.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct Play {
+    pub opponent: usize,
+    pub counter: usize,
+}
+
+impl Rules {
+    /// The rules of the puzzle: Rock, Paper and Scissors played as A, B and C
+    /// by the opponent and X, Y and Z by us.
+    pub fn classic() -> Rules {
+        let shape = |name: &str, score, opponent: &str, response: &str| ShapeRule {
+            name: name.to_string(),
+            score,
+            opponent: opponent.to_string(),
+            response: response.to_string(),
+        };
+
+        return Rules {
+            shapes: vec![shape("Rock", 1, "A", "X"), shape("Paper", 2, "B", "Y"), shape("Scissors", 3, "C", "Z")],
+            outcomes: OutcomeCodes { loss: "X".to_string(), draw: "Y".to_string(), win: "Z".to_string() },
+            scores: OutcomeScores::default(),
+        };
+    }
+
+    /// Reads rules from TOML, checking that every shape has its own name and
+    /// codes and that the cycle has an odd number of shapes, at least three,
+    /// so that every pair of different shapes has a winner and every outcome
+    /// can be aimed for. Codes are single guide columns, so they cannot be
+    /// empty or hold whitespace.
+    pub fn from_toml(content: &str) -> Result<Rules, String> {
+        let rules: Rules = toml::from_str(content).map_err(|err| err.to_string())?;
+
+        if rules.shapes.len() < 3 || rules.shapes.len().is_multiple_of(2) {
+            return Err(format!("Expected an odd number of shapes, at least 3, but found {}", rules.shapes.len()));
+        }
+
+        let codes = rules.shapes.iter().flat_map(|shape| [&shape.opponent, &shape.response]);
+        let outcome_codes = [&rules.outcomes.loss, &rules.outcomes.draw, &rules.outcomes.win];
+        if let Some(code) = codes.chain(outcome_codes).find(|code| code.is_empty() || code.contains(char::is_whitespace)) {
+            return Err(format!("Code {:?} cannot be empty or contain whitespace", code));
+        }
+
+        let mut names = HashSet::new();
+        let mut opponents = HashSet::new();
+        let mut responses = HashSet::new();
+        for shape in &rules.shapes {
+            if !names.insert(shape.name.to_uppercase()) {
+                return Err(format!("Shape {} is listed more than once", shape.name));
+            }
+            if !opponents.insert(shape.opponent.to_uppercase()) {
+                return Err(format!("Opponent code {} is used by more than one shape", shape.opponent));
+            }
+            if !responses.insert(shape.response.to_uppercase()) {
+                return Err(format!("Response code {} is used by more than one shape", shape.response));
+            }
+        }
+
+        let outcomes: HashSet<String> = [&rules.outcomes.loss, &rules.outcomes.draw, &rules.outcomes.win].iter().map(|code| code.to_uppercase()).collect();
+        if outcomes.len() != 3 {
+            return Err("Every outcome needs its own code".to_string());
+        }
+
+        return Ok(rules);
+    }
+
+    pub fn outcome(&self, play: Play) -> Outcome {
+        return cyclic_outcome(self.shapes.len(), play.opponent, play.counter);
+    }
+
+    pub fn score(&self, play: Play) -> i32 {
+        let outcome_score = match self.outcome(play) {
+            Outcome::Win => self.scores.win,
+            Outcome::Draw => self.scores.draw,
+            Outcome::Loss => self.scores.loss,
+        };
+        return self.shapes[play.counter].score + outcome_score;
+    }
+
+    pub fn total_score(&self, plays: &[Play]) -> i32 {
+        return plays.iter().map(|&play| self.score(play)).sum();
+    }
+
+    /// Reads a strategy guide written with these rules' codes, with its
+    /// second column read as `interpretation`.
+    pub fn parse_plays(&self, source: &str, interpretation: GuideInterpretation, recovery: &mut Recovery) -> Result<Vec<Play>, ParseError> {
+        return round_lines(source)
+            .map(|(_, line)| recovery.recover(self.parse_play(source, line, interpretation)))
+            .filter_map(|play| play.transpose())
+            .collect();
+    }
+
+    fn parse_play(&self, source: &str, line: &str, interpretation: GuideInterpretation) -> Result<Play, ParseError> {
+        let columns: Vec<&str> = line.split_whitespace().collect();
+        match columns[..] {
+            [opponent_raw, response_raw] => {
+                let opponent = self
+                    .shapes
+                    .iter()
+                    .position(|shape| shape.opponent.eq_ignore_ascii_case(opponent_raw))
+                    .ok_or_else(|| ParseError::spanning(source, opponent_raw, format!("Unknown opponent move {}, expected {}", opponent_raw, self.codes(|s| &s.opponent))))?;
+
+                let counter = match interpretation {
+                    GuideInterpretation::Shape => self
+                        .shapes
+                        .iter()
+                        .position(|shape| shape.response.eq_ignore_ascii_case(response_raw))
+                        .ok_or_else(|| ParseError::spanning(source, response_raw, format!("Unknown response {}, expected {}", response_raw, self.codes(|s| &s.response))))?,
+                    GuideInterpretation::Outcome => {
+                        let outcome = self
+                            .outcome_for_code(response_raw)
+                            .ok_or_else(|| ParseError::spanning(source, response_raw, format!("Unknown response {}, expected {}", response_raw, list_codes(&[&self.outcomes.loss, &self.outcomes.draw, &self.outcomes.win]))))?;
+                        cyclic_counter(self.shapes.len(), outcome, opponent)
+                    }
+                };
+
+                return Ok(Play { opponent, counter });
+            },
+            _ => Err(ParseError::spanning(source, line.trim(), "Expected an opponent move and a response separated by a space"))
+        }
+    }
+
+    fn outcome_for_code(&self, code: &str) -> Option<Outcome> {
+        match code {
+            _ if self.outcomes.loss.eq_ignore_ascii_case(code) => Some(Outcome::Loss),
+            _ if self.outcomes.draw.eq_ignore_ascii_case(code) => Some(Outcome::Draw),
+            _ if self.outcomes.win.eq_ignore_ascii_case(code) => Some(Outcome::Win),
+            _ => None,
+        }
+    }
+
+    fn codes(&self, code: impl Fn(&ShapeRule) -> &String) -> String {
+        return list_codes(&self.shapes.iter().map(code).collect::<Vec<&String>>());
+    }
+}
+
+/// `codes` written the way the puzzle's own errors list them, as "X, Y or Z".
+fn list_codes(codes: &[&String]) -> String {
+    match codes {
+        [] => String::new(),
+        [only] => only.to_string(),
+        [rest @ .., last] => format!("{} or {}", rest.iter().map(|code| code.as_str()).collect::<Vec<&str>>().join(", "), last),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    const RPSLS: &str = include_str!("../rpsls.toml");
+
+    #[test]
+    fn classic_rules_score_the_sample_both_ways() {
+        let rules = Rules::classic();
+        let score = |interpretation| rules.total_score(&rules.parse_plays("A Y\nB X\nC Z", interpretation, &mut Recovery::strict()).unwrap());
+
+        assert_eq!((score(GuideInterpretation::Shape), score(GuideInterpretation::Outcome)), (15, 12));
+    }
+
+    #[test]
+    fn skips_the_same_lines_as_the_round_count() {
+        let source = "A Y\n\n  \nB Q\nC Z\n";
+        let plays = Rules::classic().parse_plays(source, GuideInterpretation::Shape, &mut Recovery::lenient()).unwrap();
+
+        assert_eq!((plays.len(), crate::count_round_lines(source)), (2, 3));
+    }
+
+    #[test]
+    fn lists_expected_codes_like_the_classic_parser() {
+        let mut recovery = Recovery::strict();
+        let classic = crate::parse_rounds("A Q", &mut recovery).err().unwrap();
+        let shape = Rules::classic().parse_plays("A Q", GuideInterpretation::Shape, &mut recovery).unwrap_err();
+        let outcome = Rules::classic().parse_plays("A Q", GuideInterpretation::Outcome, &mut recovery).unwrap_err();
+
+        assert_eq!(shape.message, classic.message);
+        assert_eq!(outcome.message, classic.message);
+    }
+
+    #[test]
+    fn every_rpsls_shape_beats_two_others() {
+        let rules = Rules::from_toml(RPSLS).unwrap();
+        let spock = rules.shapes.iter().position(|s| s.name == "Spock").unwrap();
+        let rock = rules.shapes.iter().position(|s| s.name == "Rock").unwrap();
+
+        assert_eq!(rules.outcome(Play { opponent: rock, counter: spock }), Outcome::Win);
+        for counter in 0..rules.shapes.len() {
+            let wins = (0..rules.shapes.len()).filter(|&opponent| rules.outcome(Play { opponent, counter }) == Outcome::Win).count();
+            assert_eq!(wins, 2, "{}", rules.shapes[counter].name);
+        }
+    }
+
+    #[test]
+    fn rejects_an_even_number_of_shapes() {
+        let rules = "[outcomes]\nloss = \"X\"\ndraw = \"Y\"\nwin = \"Z\"\n\n\
+            [[shape]]\nname = \"Rock\"\nscore = 1\nopponent = \"A\"\nresponse = \"X\"\n\n\
+            [[shape]]\nname = \"Paper\"\nscore = 2\nopponent = \"B\"\nresponse = \"Y\"\n";
+
+        assert!(Rules::from_toml(rules).unwrap_err().contains("odd number"));
+    }
+
+    #[test]
+    fn rejects_a_single_shape() {
+        let rules = "[outcomes]\nloss = \"X\"\ndraw = \"Y\"\nwin = \"Z\"\n\n\
+            [[shape]]\nname = \"Rock\"\nscore = 1\nopponent = \"A\"\nresponse = \"X\"\n";
+
+        assert!(Rules::from_toml(rules).unwrap_err().contains("at least 3"));
+    }
+
+    #[test]
+    fn rejects_empty_and_whitespace_codes() {
+        let blank_win = RPSLS.replace("win = \"Z\"", "win = \" \"");
+        let empty_opponent = RPSLS.replacen("opponent = \"A\"", "opponent = \"\"", 1);
+
+        assert!(Rules::from_toml(&blank_win).unwrap_err().contains("cannot be empty"));
+        assert!(Rules::from_toml(&empty_opponent).unwrap_err().contains("cannot be empty"));
+    }
+}
@@ -0,0 +1,132 @@
+use std::str::FromStr;
+
+use crate::rules::{Play, Rules};
+
+/// What the solver knows about the opponent's move when choosing a counter.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum OpponentModel {
+    /// Every opponent move is known in advance, so each one gets its own counter.
+    Fixed,
+    /// Opponent moves are drawn at random with the frequencies they have in the
+    /// guide, so a single shape is played every round.
+    Distribution,
+}
+
+impl OpponentModel {
+    pub const ALL: [OpponentModel; 2] = [OpponentModel::Fixed, OpponentModel::Distribution];
+
+    pub fn describe(&self) -> &'static str {
+        match self {
+            OpponentModel::Fixed => "knowing each opponent move",
+            OpponentModel::Distribution => "drawing opponent moves from the guide's frequencies",
+        }
+    }
+}
+
+impl FromStr for OpponentModel {
+    type Err = String;
+
+    fn from_str(value: &str) -> Result<OpponentModel, String> {
+        match value.trim() {
+            "fixed" => Ok(OpponentModel::Fixed),
+            "distribution" => Ok(OpponentModel::Distribution),
+            other => Err(format!("Unknown opponent model {}. Can be fixed or distribution", other)),
+        }
+    }
+}
+
+/// The counter-moves that score the most against a guide's opponent moves.
+pub struct Strategy {
+    pub model: OpponentModel,
+    /// The shape to play against each opponent shape, by position in the cycle.
+    pub counters: Vec<usize>,
+    /// The expected total score over every round of the guide.
+    pub expected_total: f64,
+    pub rounds: usize,
+}
+
+impl Strategy {
+    pub fn expected_per_round(&self) -> f64 {
+        match self.rounds {
+            0 => 0.0,
+            rounds => self.expected_total / rounds as f64,
+        }
+    }
+
+    /// How much more the strategy is expected to score than `total`.
+    pub fn gain_over(&self, total: i32) -> f64 {
+        return self.expected_total - total as f64;
+    }
+}
+
+/// Works out the best counter-moves to the opponent moves in `plays` under
+/// `model`. Ties go to the shape listed first in `rules`.
+pub fn solve_strategy(rules: &Rules, plays: &[Play], model: OpponentModel) -> Strategy {
+    let shapes = rules.shapes.len();
+    let mut frequencies = vec![0usize; shapes];
+    for play in plays {
+        frequencies[play.opponent] += 1;
+    }
+
+    // The total from playing `counter` against every opponent move.
+    let score_against_all = |counter: usize| -> i64 {
+        return (0..shapes).map(|opponent| frequencies[opponent] as i64 * rules.score(Play { opponent, counter }) as i64).sum();
+    };
+
+    let counters = match model {
+        OpponentModel::Fixed => (0..shapes).map(|opponent| best_by(shapes, |counter| rules.score(Play { opponent, counter }) as i64)).collect(),
+        OpponentModel::Distribution => vec![best_by(shapes, score_against_all); shapes],
+    };
+
+    let expected_total = (0..shapes)
+        .map(|opponent| frequencies[opponent] as f64 * rules.score(Play { opponent, counter: counters[opponent] }) as f64)
+        .sum();
+
+    return Strategy { model, counters, expected_total, rounds: plays.len() };
+}
+
+/// The first of `shapes` with the highest `score`.
+fn best_by(shapes: usize, score: impl Fn(usize) -> i64) -> usize {
+    return (0..shapes).rev().max_by_key(|&shape| score(shape)).unwrap_or(0);
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::GuideInterpretation;
+    use aoc_core::Recovery;
+
+    fn sample_plays(rules: &Rules) -> Vec<Play> {
+        return rules.parse_plays("A Y\nB X\nC Z", GuideInterpretation::Shape, &mut Recovery::strict()).unwrap();
+    }
+
+    #[test]
+    fn counters_each_known_move_with_the_shape_that_beats_it() {
+        let rules = Rules::classic();
+        let strategy = solve_strategy(&rules, &sample_plays(&rules), OpponentModel::Fixed);
+
+        assert_eq!(strategy.counters, vec![1, 2, 0]);
+        assert_eq!(strategy.expected_total, 24.0);
+        assert_eq!(strategy.gain_over(15), 9.0);
+    }
+
+    #[test]
+    fn plays_one_shape_against_a_distribution() {
+        let rules = Rules::classic();
+        let strategy = solve_strategy(&rules, &sample_plays(&rules), OpponentModel::Distribution);
+
+        assert_eq!(strategy.counters, vec![2, 2, 2]);
+        assert_eq!(strategy.expected_per_round(), 6.0);
+    }
+
+    #[test]
+    fn solves_from_every_round_of_a_guide_read_as_shapes() {
+        let rules = Rules::from_toml(include_str!("../rpsls.toml")).unwrap();
+        let plays = rules.parse_plays("A V\nB W\nC X\nE Z", GuideInterpretation::Shape, &mut Recovery::strict()).unwrap();
+        let strategy = solve_strategy(&rules, &plays, OpponentModel::Fixed);
+
+        assert_eq!(strategy.rounds, 4);
+        assert_eq!(strategy.expected_total, 42.0);
+        assert_eq!(strategy.gain_over(rules.total_score(&plays)), 19.0);
+    }
+}
@@ -0,0 +1,164 @@
+use crate::{Game, GuideInterpretation, Round, Shape};
+
+pub const WIN_POINTS: u32 = 3;
+pub const DRAW_POINTS: u32 = 1;
+
+/// A strategy guide reduced to the shapes it plays, one per round.
+pub struct Entrant {
+    pub name: String,
+    /// The line of each round in the guide with the shape played on it, in
+    /// line order.
+    pub moves: Vec<(usize, Shape)>,
+}
+
+impl Entrant {
+    /// The shapes `rounds` play when read as `interpretation`. Read as
+    /// outcomes, the moves depend on the guide's own opponent column.
+    pub fn from_rounds(name: impl Into<String>, rounds: &[Round], interpretation: GuideInterpretation) -> Entrant {
+        let moves = rounds.iter().map(|round| (round.line(), round.to_game(interpretation).counter_move)).collect();
+        return Entrant { name: name.into(), moves };
+    }
+
+    /// An entrant playing a shape picked at random on every line up to
+    /// `lines`. The same `seed` always gives the same moves.
+    pub fn random(name: impl Into<String>, lines: usize, seed: u64) -> Entrant {
+        let mut state = seed;
+        let moves = (1..=lines).map(|line| (line, Shape::ALL[(split_mix(&mut state) % Shape::ALL.len() as u64) as usize])).collect();
+        return Entrant { name: name.into(), moves };
+    }
+}
+
+/// SplitMix64, which is plenty for picking baseline moves.
+fn split_mix(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    return z ^ (z >> 31);
+}
+
+/// The scores of `first` and `second` playing their moves against each other,
+/// round by round. Only rounds on a line both guides play are played, so a
+/// round one guide skipped is never played against the next round of the
+/// other.
+pub fn play_match(first: &Entrant, second: &Entrant) -> (i32, i32) {
+    let mut scores = (0, 0);
+    let (mut first_index, mut second_index) = (0, 0);
+
+    while let (Some(&(first_line, first_move)), Some(&(second_line, second_move))) = (first.moves.get(first_index), second.moves.get(second_index)) {
+        match first_line.cmp(&second_line) {
+            std::cmp::Ordering::Less => first_index += 1,
+            std::cmp::Ordering::Greater => second_index += 1,
+            std::cmp::Ordering::Equal => {
+                let first_game = Game { opponent_move: second_move, counter_move: first_move };
+                let second_game = Game { opponent_move: first_move, counter_move: second_move };
+                scores = (scores.0 + first_game.get_total_score(), scores.1 + second_game.get_total_score());
+                first_index += 1;
+                second_index += 1;
+            },
+        }
+    }
+
+    return scores;
+}
+
+/// A row of the league table.
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct Standing {
+    pub name: String,
+    pub played: u32,
+    pub wins: u32,
+    pub draws: u32,
+    pub losses: u32,
+    pub points: u32,
+    /// The total score of every match the entrant played.
+    pub score_for: i64,
+    /// The total score of its opponents in those matches.
+    pub score_against: i64,
+}
+
+impl Standing {
+    fn record(&mut self, score: i32, opponent_score: i32) {
+        self.played += 1;
+        self.score_for += score as i64;
+        self.score_against += opponent_score as i64;
+
+        match score.cmp(&opponent_score) {
+            std::cmp::Ordering::Greater => {
+                self.wins += 1;
+                self.points += WIN_POINTS;
+            },
+            std::cmp::Ordering::Equal => {
+                self.draws += 1;
+                self.points += DRAW_POINTS;
+            },
+            std::cmp::Ordering::Less => self.losses += 1,
+        }
+    }
+}
+
+/// Plays every entrant once against every other and returns the league
+/// table, ordered by points, then score difference, then name.
+pub fn round_robin(entrants: &[Entrant]) -> Vec<Standing> {
+    let mut table: Vec<Standing> = entrants.iter().map(|entrant| Standing { name: entrant.name.clone(), ..Standing::default() }).collect();
+
+    for first in 0..entrants.len() {
+        for second in first + 1..entrants.len() {
+            let (first_score, second_score) = play_match(&entrants[first], &entrants[second]);
+            table[first].record(first_score, second_score);
+            table[second].record(second_score, first_score);
+        }
+    }
+
+    table.sort_by(|a, b| {
+        return b.points
+            .cmp(&a.points)
+            .then((b.score_for - b.score_against).cmp(&(a.score_for - a.score_against)))
+            .then(a.name.cmp(&b.name));
+    });
+    return table;
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse_rounds;
+    use aoc_core::Recovery;
+
+    fn always(name: &str, shape: Shape, rounds: usize) -> Entrant {
+        return Entrant { name: name.to_string(), moves: (1..=rounds).map(|line| (line, shape)).collect() };
+    }
+
+    #[test]
+    fn scores_a_match_over_the_shorter_guide() {
+        assert_eq!(play_match(&always("paper", Shape::Paper, 3), &always("rock", Shape::Rock, 2)), (16, 2));
+        assert_eq!(play_match(&always("rock", Shape::Rock, 2), &always("rock", Shape::Rock, 2)), (8, 8));
+    }
+
+    #[test]
+    fn plays_rounds_on_the_same_line_against_each_other() {
+        let mut recovery = Recovery::lenient();
+        let skipped = Entrant::from_rounds("skipped", &parse_rounds("A X\nA Q\nA Y", &mut recovery).unwrap(), GuideInterpretation::Shape);
+        let full = Entrant::from_rounds("full", &parse_rounds("A X\nA Z\nA X", &mut recovery).unwrap(), GuideInterpretation::Shape);
+
+        assert_eq!(play_match(&skipped, &full), (4 + 8, 4 + 1));
+    }
+
+    #[test]
+    fn ranks_entrants_by_points() {
+        let entrants = [always("rock", Shape::Rock, 4), always("paper", Shape::Paper, 4), always("scissors", Shape::Scissors, 4), always("also rock", Shape::Rock, 4)];
+        let table = round_robin(&entrants);
+
+        let rows: Vec<(&str, u32, u32, u32, u32)> = table.iter().map(|s| (s.name.as_str(), s.wins, s.draws, s.losses, s.points)).collect();
+        assert_eq!(rows[0], ("paper", 2, 0, 1, 6));
+        assert_eq!(table.iter().map(|s| s.played).collect::<Vec<u32>>(), vec![3, 3, 3, 3]);
+    }
+
+    #[test]
+    fn random_entrants_repeat_for_the_same_seed() {
+        let moves = |seed| Entrant::random("random", 50, seed).moves;
+
+        assert_eq!(moves(7), moves(7));
+        assert_ne!(moves(7), moves(8));
+    }
+}
@@ -0,0 +1,69 @@
+//! Writes a large day 3 input for benchmarking to stdout, shaped like the
+//! puzzle's: every rucksack's two compartments share exactly one item and
+//! every group of three rucksacks shares exactly one badge.
+//!
+//! cargo run --release -p day-3 --example generate -- 60000 2022 > day-3/large.input
+
+const ITEMS: &[u8] = b"abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ";
+
+fn main() {
+    let mut args = std::env::args().skip(1);
+    let lines: usize = args.next().map(|value| value.parse().expect("the number of lines must be a number")).unwrap_or(60000);
+    let mut state: u64 = args.next().map(|value| value.parse().expect("the seed must be a number")).unwrap_or(2022);
+
+    let mut output = String::new();
+    for _ in 0..lines.div_ceil(3) {
+        for rucksack in group(&mut state) {
+            output.push_str(&rucksack);
+            output.push('\n');
+        }
+    }
+    print!("{}", output);
+}
+
+/// Three rucksacks whose only common item is the badge. Each one draws its
+/// other items from its own third of the remaining items.
+fn group(state: &mut u64) -> Vec<String> {
+    let mut items = ITEMS.to_vec();
+    shuffle(&mut items, state);
+    let badge = items[0];
+
+    return items[1..].chunks(17).map(|pool| rucksack(badge, pool, state)).collect();
+}
+
+/// A rucksack holding `badge` in its first compartment, one item from `pool`
+/// in both, and otherwise items from separate halves of `pool`.
+fn rucksack(badge: u8, pool: &[u8], state: &mut u64) -> String {
+    let shared = pool[0];
+    let (first_pool, second_pool) = pool[1..].split_at(pool.len() / 2);
+    let size = 8 + (next(state) % 17) as usize;
+
+    let mut first = vec![badge, shared];
+    let mut second = vec![shared];
+    while first.len() < size {
+        first.push(first_pool[(next(state) % first_pool.len() as u64) as usize]);
+    }
+    while second.len() < size {
+        second.push(second_pool[(next(state) % second_pool.len() as u64) as usize]);
+    }
+    shuffle(&mut first, state);
+    shuffle(&mut second, state);
+
+    first.append(&mut second);
+    return String::from_utf8(first).unwrap();
+}
+
+fn shuffle(items: &mut [u8], state: &mut u64) {
+    for index in (1..items.len()).rev() {
+        items.swap(index, (next(state) % (index as u64 + 1)) as usize);
+    }
+}
+
+/// SplitMix64, so that the same seed always gives the same input.
+fn next(state: &mut u64) -> u64 {
+    *state = state.wrapping_add(0x9e3779b97f4a7c15);
+    let mut z = *state;
+    z = (z ^ (z >> 30)).wrapping_mul(0xbf58476d1ce4e5b9);
+    z = (z ^ (z >> 27)).wrapping_mul(0x94d049bb133111eb);
+    return z ^ (z >> 31);
+}
@@ -0,0 +1,376 @@
+use std::ops::BitAnd;
+use std::path::{Path, PathBuf};
+use aoc_core::{Answer, Error, ParseError, Recovery, Solution};
+
+pub mod report;
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<RuckSack>;
+
+    const DAY: u8 = 3;
+
+    fn default_input() -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join("day3.input");
+    }
+
+    fn parse_with(input: &str, recovery: &mut Recovery) -> Result<Vec<RuckSack>, ParseError> {
+        return get_rucksacks(input, recovery);
+    }
+
+    fn part1(rucksacks: &Vec<RuckSack>) -> Result<Answer, Error> {
+        return Ok(calculate_duplicate_priority_score(rucksacks).into());
+    }
+
+    fn part2(rucksacks: &Vec<RuckSack>) -> Result<Answer, Error> {
+        return Ok(find_badges(rucksacks, GROUP_SIZE).checked_score()?.into());
+    }
+}
+
+/// How many compartments the puzzle's rucksacks have.
+pub const COMPARTMENTS: usize = 2;
+
+/// How many elves share a badge in the puzzle.
+pub const GROUP_SIZE: usize = 3;
+
+pub fn calculate_duplicate_priority_score(rucksacks: &[RuckSack]) -> i32 {
+    return rucksacks.iter().map(|rucksack| rucksack.get_duplicate_score() ).map(|(_, score)| score).sum()
+}
+
+pub fn get_rucksacks(value: &str, recovery: &mut Recovery) -> Result<Vec<RuckSack>, ParseError> {
+    return get_rucksacks_with(value, COMPARTMENTS, recovery);
+}
+
+/// Reads rucksacks split into `compartments` equal compartments. A rucksack
+/// that cannot be split evenly is an error, as is asking for no compartments.
+pub fn get_rucksacks_with(value: &str, compartments: usize, recovery: &mut Recovery) -> Result<Vec<RuckSack>, ParseError> {
+    if compartments == 0 {
+        return Err(ParseError::new("A rucksack needs at least one compartment"));
+    }
+
+    return value
+        .split("\n")
+        .map(|line| line.trim())
+        .enumerate()
+        .filter(|(_, trimmed_line)| !trimmed_line.is_empty())
+        .map(|(index, trimmed_line)| recovery.recover(parse_rucksack(value, trimmed_line, index + 1, compartments)))
+        .filter_map(|rucksack| rucksack.transpose())
+        .collect();
+}
+
+fn parse_rucksack(source: &str, line: &str, line_number: usize, compartments: usize) -> Result<RuckSack, ParseError> {
+    match line.char_indices().find(|(_, item)| !item.is_ascii_alphabetic()) {
+        Some((index, item)) => {
+            let token = &line[index..index + item.len_utf8()];
+            return Err(ParseError::spanning(source, token, format!("Unknown item {:?}, expected a letter", item)));
+        },
+        None if !line.len().is_multiple_of(compartments) => {
+            return Err(ParseError::spanning(source, line, format!("{} items cannot be split into {} equal compartments", line.len(), compartments)));
+        },
+        None => Ok(RuckSack::create_with_compartments(line, line_number, compartments))
+    }
+}
+
+#[derive(Debug)]
+pub struct RuckSack {
+    line: usize,
+    /// The items as written in the input. Scoring only needs the item sets;
+    /// this is kept for the report, which lists each rucksack's items.
+    contents: String,
+    items: ItemSet,
+    compartments: Vec<Compartment>,
+}
+
+impl RuckSack {
+    /// Splits `items`, read from `line` of the input, whose length must divide
+    /// by `compartments`, into that many equal compartments.
+    fn create_with_compartments(items: &str, line: usize, compartments: usize) -> RuckSack{
+        let size = items.len() / compartments;
+        let compartments = (0..compartments).map(|index| Compartment::create(&items[index * size..(index + 1) * size])).collect();
+
+        let result = RuckSack { line, contents: items.to_string(), items: ItemSet::from_items(items), compartments };
+        return result;
+    }
+
+    /// The line of the input the rucksack was read from.
+    pub fn line(&self) -> usize {
+        return self.line;
+    }
+
+    /// The items found in every compartment.
+    pub fn get_shared_items(&self) -> ItemSet {
+        return self.compartments.iter().fold(ItemSet::ALL, |shared, compartment| shared & compartment.items);
+    }
+
+    pub fn get_duplicates(&self) -> Vec<char> {
+        return self.get_shared_items().items().collect();
+    }
+
+    pub fn get_duplicate_score(&self) -> (Vec<char>, i32) {
+        let shared = self.get_shared_items();
+        return (shared.items().collect(), shared.priority_sum());
+    }
+
+    /// Every item in the rucksack, whichever compartment it is in.
+    pub fn get_items(&self) -> ItemSet {
+        return self.items;
+    }
+
+}
+
+/// Scores the badge of every group of three rucksacks, the items all three
+/// carry. A trailing group of fewer than three has no badge.
+pub fn group_and_score_rucksacks(rucksacks: &[RuckSack]) -> i32 {
+    return find_badges(rucksacks, GROUP_SIZE).score();
+}
+
+/// The badges of rucksacks grouped in order, `group_size` at a time.
+pub struct Badges {
+    /// The items every rucksack in each full group carries.
+    pub badges: Vec<ItemSet>,
+    /// How many rucksacks were left over after the last full group. They have
+    /// no badge and are not scored.
+    pub leftover: usize,
+    pub group_size: usize,
+}
+
+impl Badges {
+    pub fn score(&self) -> i32 {
+        return self.badges.iter().map(|badge| badge.priority_sum()).sum();
+    }
+
+    /// The score of every group, or an error when the last group is not
+    /// full, since its badge cannot be known.
+    pub fn checked_score(&self) -> Result<i32, Error> {
+        match self.leftover {
+            0 => return Ok(self.score()),
+            leftover => return Err(Error::Solve(format!("The last group has only {} of {} rucksacks", leftover, self.group_size))),
+        }
+    }
+}
+
+/// Finds the badge of every full group of `group_size` rucksacks. With a
+/// group size of zero there are no groups and every rucksack is left over.
+pub fn find_badges(rucksacks: &[RuckSack], group_size: usize) -> Badges {
+    if group_size == 0 {
+        return Badges { badges: Vec::new(), leftover: rucksacks.len(), group_size };
+    }
+
+    let groups = rucksacks.chunks_exact(group_size);
+    let leftover = groups.remainder().len();
+    let badges = groups.map(|group| group.iter().fold(ItemSet::ALL, |shared, rucksack| shared & rucksack.get_items())).collect();
+
+    return Badges { badges, leftover, group_size };
+}
+
+/// A set of items held as one bit per priority, so that finding the items
+/// two sets share is a single AND.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq)]
+pub struct ItemSet(u64);
+
+impl ItemSet {
+    /// Every item there is, the starting point for intersecting sets.
+    pub const ALL: ItemSet = ItemSet(((1 << 52) - 1) << 1);
+
+    /// The set of `items`. Anything that is not a letter is left out.
+    pub fn from_items(items: &str) -> ItemSet {
+        return ItemSet(items.bytes().fold(0, |bits, item| bits | item_bit(item)));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        return self.0 == 0;
+    }
+
+    pub fn len(&self) -> usize {
+        return self.0.count_ones() as usize;
+    }
+
+    pub fn contains(&self, item: char) -> bool {
+        return item.is_ascii() && self.0 & item_bit(item as u8) != 0;
+    }
+
+    /// The priorities of the items in the set, lowest first.
+    pub fn priorities(&self) -> impl Iterator<Item = i32> {
+        let bits = self.0;
+        return (1..=52).filter(move |priority| bits & (1 << priority) != 0);
+    }
+
+    /// The items in the set, in priority order: a to z, then A to Z.
+    pub fn items(&self) -> impl Iterator<Item = char> {
+        return self.priorities().map(get_priority_char);
+    }
+
+    pub fn priority_sum(&self) -> i32 {
+        return self.priorities().sum();
+    }
+}
+
+impl BitAnd for ItemSet {
+    type Output = ItemSet;
+
+    fn bitand(self, other: ItemSet) -> ItemSet {
+        return ItemSet(self.0 & other.0);
+    }
+}
+
+fn item_bit(item: u8) -> u64 {
+    match get_char_priority(&(item as char)) {
+        0 => 0,
+        priority => 1 << priority,
+    }
+}
+
+#[derive(Debug)]
+pub struct Compartment {
+    items: ItemSet,
+}
+
+impl Compartment {
+    fn create(items: &str) -> Compartment {
+        let compartment =  Compartment { items: ItemSet::from_items(items) };
+        return compartment;
+    }
+}
+
+fn get_char_priority(value: &char) -> i32 {
+    let ascii_value = *value as u8;
+    match ascii_value  {
+         _ if (65..=90).contains(&ascii_value) => {
+             return (ascii_value as i32) - 65 + 1 + 26
+         },
+         _ if (97..=122).contains(&ascii_value) => {
+             return (ascii_value as i32) - 97 + 1
+
+         },
+         _ => return 0
+    }
+}
+
+fn get_priority_char(priority: i32) -> char {
+    match priority {
+        1..=26 => return (b'a' + (priority - 1) as u8) as char,
+        _ => return (b'A' + (priority - 27) as u8) as char,
+    }
+}
+
+
+#[test]
+fn calculates_sample_score(){
+    let sample = "vJrwpWtwJgWrhcsFMMfFFhFp\n
+    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n
+    PmmdzqPrVvPwwTWBwg\n
+    wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n
+    ttgJtRGJQctTZtZT\n
+    CrZsJsPPZsGzwwsLwLmpwMDw\n";
+
+    let rucksacks: Vec<RuckSack> = get_rucksacks(sample, &mut Recovery::strict()).unwrap();
+    let total_score: i32 = calculate_duplicate_priority_score(&rucksacks);
+    assert_eq!(total_score, 157)
+}
+
+#[test]
+fn calculates_group_badge(){
+    let sample = "vJrwpWtwJgWrhcsFMMfFFhFp\n
+    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n
+    PmmdzqPrVvPwwTWBwg\n
+    wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n
+    ttgJtRGJQctTZtZT\n
+    CrZsJsPPZsGzwwsLwLmpwMDw\n";
+
+    let rucksacks = get_rucksacks(sample, &mut Recovery::strict()).unwrap();
+    let total_score: i32 = group_and_score_rucksacks(&rucksacks);
+    assert_eq!(total_score, 70)
+}
+
+#[test]
+fn gives_a_trailing_partial_group_no_badge(){
+    let sample = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\n";
+
+    let rucksacks = get_rucksacks(sample, &mut Recovery::strict()).unwrap();
+    assert_eq!(group_and_score_rucksacks(&rucksacks[..3]), 18);
+    assert_eq!(group_and_score_rucksacks(&rucksacks[..4]), 18);
+    assert_eq!(group_and_score_rucksacks(&rucksacks), 18);
+}
+
+#[test]
+fn rucksack_creates_correct_compartments(){
+    let rucksack = RuckSack::create_with_compartments("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL", 1, 2);
+    match &rucksack.compartments[..] {
+        [first, second] => {
+            assert_eq!(first.items, ItemSet::from_items("jqHRNqRjqzjGDLGL"));  //jNqGzHDRL
+            assert_eq!(second.items, ItemSet::from_items("rsFMfFZSrLrFZsSL")); //FrfMLsZ
+            assert_eq!(rucksack.get_duplicates(), vec!['L']);
+        },
+        _ => {
+            panic!("This should never happen!")
+        }
+    }
+}
+
+#[test]
+fn reports_items_that_are_not_letters(){
+    let err = get_rucksacks("vJrwpWtwJgWrhcsFMMfFFhFp\nPmmdz1PrVvPwwTWBwg\n", &mut Recovery::strict()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.column, span.text.as_str()), (2, 6, "1"));
+}
+
+#[test]
+fn skips_rucksack_with_unknown_item_when_lenient(){
+    let mut recovery = Recovery::lenient();
+    let rucksacks = get_rucksacks("vJrwpWtwJgWrhcsFMMfFFhFp\nPmmdz1PrVvPwwTWBwg\n", &mut recovery).unwrap();
+
+    assert_eq!(rucksacks.len(), 1);
+    assert_eq!(recovery.skipped()[0].span.as_ref().unwrap().line, 2);
+}
+
+#[test]
+fn matches_recorded_answers(){
+    let input = std::fs::read_to_string(Puzzle::default_input()).unwrap();
+
+    for part in aoc_core::Part::ALL {
+        aoc_core::answers::assert_recorded::<Puzzle>(part, &input);
+    }
+}
+
+#[test]
+fn item_sets_hold_one_bit_per_priority(){
+    let items = ItemSet::from_items("vJrwpWtwJgWrhcsFMMfFFhFp");
+
+    assert_eq!(items.len(), 14);
+    assert!(items.contains('p') && items.contains('J') && !items.contains('a'));
+    assert_eq!((items & ItemSet::from_items("aApP")).items().collect::<String>(), "p");
+    assert_eq!(ItemSet::from_items("aZ").priority_sum(), 1 + 52);
+    assert_eq!(ItemSet::ALL.items().collect::<String>(), "abcdefghijklmnopqrstuvwxyzABCDEFGHIJKLMNOPQRSTUVWXYZ");
+}
+
+#[test]
+fn splits_rucksacks_into_equal_compartments(){
+    let rucksacks = get_rucksacks_with("aabXcbXc\nabcd\n", 4, &mut Recovery::strict()).unwrap();
+
+    assert_eq!(rucksacks[0].compartments.len(), 4);
+    assert_eq!(rucksacks[0].get_duplicates(), Vec::<char>::new());
+    assert_eq!(rucksacks[1].compartments.iter().map(|c| c.items.len()).collect::<Vec<usize>>(), vec![1, 1, 1, 1]);
+    assert_eq!(get_rucksacks_with("abXbaX\n", 3, &mut Recovery::strict()).unwrap()[0].get_duplicates(), Vec::<char>::new());
+    assert_eq!(get_rucksacks_with("XaXbXc\n", 3, &mut Recovery::strict()).unwrap()[0].get_duplicates(), vec!['X']);
+}
+
+#[test]
+fn reports_rucksack_that_cannot_be_split_evenly(){
+    let err = get_rucksacks_with("abcdef\nabcde\n", 2, &mut Recovery::strict()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.text.as_str()), (2, "abcde"));
+}
+
+#[test]
+fn reports_rucksacks_left_over_after_the_last_group(){
+    let rucksacks = get_rucksacks("abAB\nbcBC\nbdBD\nabab\n", &mut Recovery::strict()).unwrap();
+    let badges = find_badges(&rucksacks, 2);
+
+    assert_eq!((badges.badges.len(), badges.leftover), (2, 0));
+    assert_eq!(find_badges(&rucksacks, 3).leftover, 1);
+    assert_eq!(find_badges(&rucksacks, 2).checked_score().unwrap(), 32);
+    assert_eq!(find_badges(&rucksacks, 3).checked_score().unwrap_err().to_string(), Puzzle::part2(&rucksacks).unwrap_err().to_string());
+}
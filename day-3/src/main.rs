@@ -1,188 +1,140 @@
-use std::{path::PathBuf, collections::{HashSet, HashMap}};
-use clap::Parser;
-use std::fs::{ read_to_string };
+use aoc_core::cli::{print_part, print_report, FormatArgs, InputArgs, ParseArgs, PartArgs};
+use aoc_core::recovery::skipped_report;
+use aoc_core::report::Format;
+use aoc_core::Recovery;
+use clap::builder::RangedU64ValueParser;
+use clap::error::ErrorKind;
+use clap::{CommandFactory, Parser};
+use day_3::report::report;
+use day_3::{calculate_duplicate_priority_score, find_badges, get_rucksacks_with, Puzzle, RuckSack, COMPARTMENTS, GROUP_SIZE};
 
 fn main() {
     let args = Cli::parse();
-    let content = read_to_string(&args.path);
+    if let Some(message) = args.conflict() {
+        Cli::command().error(ErrorKind::ArgumentConflict, message).exit();
+    }
 
-    match content {
+    match args.input.read() {
         Ok(value) => {
-            let rucksacks: Vec<RuckSack> = get_rucksacks(&value); 
-            let total_score = group_and_score_rucksacks(rucksacks);
+            if args.verbose {
+                return print_verbose(&args, &value);
+            }
+
+            if let Some(format) = args.format.format {
+                return print_report::<Puzzle>(&value, &args.part.parts(), args.parse.mode(), format);
+            }
 
-            println!("The sum of priority items is {}", total_score);
+            if args.compartments != COMPARTMENTS || args.group_size != GROUP_SIZE {
+                return print_custom(&args, &value);
+            }
 
+            let rucksacks: Vec<RuckSack> = match args.parse.parse_input::<Puzzle>(&value) {
+                Ok(rucksacks) => rucksacks,
+                Err(err) => return println!("{}", err.render(&value)),
+            };
+
+            match args.part.part {
+                Some(part) => print_part::<Puzzle>(part, &rucksacks),
+                None => match find_badges(&rucksacks, GROUP_SIZE).checked_score() {
+                    Ok(score) => println!("The sum of priority items is {}", score),
+                    Err(err) => println!("{}", err),
+                },
+            }
         }
         Err(err) => {
-            println!("Could not read file: {}", err)
+            println!("{}", err)
         }
     }
 }
 
+/// Reads the rucksacks in `value` with the --compartments given, reporting
+/// skipped lines and parse errors. `None` when they could not be read.
+fn read_rucksacks(args: &Cli, value: &str) -> Option<Vec<RuckSack>> {
+    let mut recovery = Recovery::new(args.parse.mode());
+    let rucksacks = get_rucksacks_with(value, args.compartments, &mut recovery);
 
-fn calculate_duplicate_priority_score(rucksacks: Vec<RuckSack>) -> i32 {
-    return rucksacks.iter().map(|rucksack| rucksack.get_duplicate_score() ).map(|(overlap, score)| score).sum()
-}
-
-fn get_rucksacks(value: &str) -> Vec<RuckSack> {
-    return value
-        .split("\n")
-        .filter_map(|line| {
-            match line.trim() {
-                trimmed_line if !trimmed_line.is_empty() => Some(RuckSack::create_with_two_compartments(trimmed_line)),
-                _ => None
-            }
-        }).collect();
-}
-
-#[derive(Debug)]
-struct RuckSack {
-    compartments: Vec<Compartment>,
-}
-
-impl RuckSack {
-    fn create_with_two_compartments(items: &str) -> RuckSack{
-        let (first, second) = items.split_at(items.len() / 2);
-
-        let result = RuckSack { compartments: vec![Compartment::create(first.clone()), Compartment::create(second.clone())] };
-        return result;
-    }
-
-    pub fn get_duplicates(&self) -> Vec<char> {
-        return get_flattened_duplicates(self.compartments.iter().map(|f| f.items.clone()).collect())
-    }
-
-    pub fn get_duplicate_score(&self) -> (Vec<char>, i32) {
-        let duplicates = self.get_duplicates();
-        let score = duplicates.iter().map(|item| get_char_priority(item)).sum();
-
-        println!("Rugsack with items {} has duplicated {} with score {}", self.compartments.iter().map(|f|f.items.clone()).flatten().collect::<String>(), duplicates.iter().collect::<String>(), score);
-
-        return (duplicates, score);
-    }
-
-    pub fn to_chars(&self) -> Vec<char> {
-        return self.compartments.iter().flat_map(|c|c.items.clone()).collect();
+    if let Some(report) = skipped_report(recovery.skipped()) {
+        eprintln!("{}", report);
     }
 
-}
-
-fn group_and_score_rucksacks(rucksacks: Vec<RuckSack>) -> i32 {
-    let rucksack_items: Vec<Vec<char>> = rucksacks.iter().map(|r|r.to_chars()).collect();
-    let item_groups: Vec<&[Vec<char>]> = rucksack_items.chunks(3).collect();
-
-    let duplicates: Vec<char> = item_groups.iter().map(|group| get_duplicates_multiple(group.to_vec() )).flatten().collect();
-
-    println!("{:?}", duplicates);
-
-    return duplicates.iter().map(|c| get_char_priority(c)).sum();
-}
-
-
-pub fn get_flattened_duplicates(items: Vec<Vec<char>>) -> Vec<char> {
-    let duplicates = get_duplicates(items);
-    return duplicates.into_iter().flatten().collect();
-}
-
-pub fn get_duplicates_multiple(items: Vec<Vec<char>>) -> Vec<char> {
-    let duplicates = get_duplicates(items);
-    let mut flattened: Vec<char> =  get_duplicates(duplicates).into_iter().flatten().collect();
-    flattened.sort();
-    flattened.dedup();
-    return flattened;
-}
-
-pub fn get_duplicates(items: Vec<Vec<char>>) -> Vec<Vec<char>> {
-    let mut all_items: Vec<HashSet<char>> = items.iter().map(|c| c.iter().cloned().collect() ).collect();
-    
-    let mut intersections: Vec<Vec<char>> = Vec::new();
-
-    while let Some(compartment_items) = &all_items.pop(){
-        for remaining_items_in_compartment in &all_items {
-            let overlap = compartment_items.intersection(&remaining_items_in_compartment);
-            intersections.push(overlap.cloned().collect());
+    match rucksacks {
+        Ok(rucksacks) => Some(rucksacks),
+        Err(err) => {
+            println!("{}", err.render(value));
+            return None;
         }
     }
-
-    return intersections;
 }
 
+/// Prints which items every rucksack and group shares, as text or, with
+/// --format json, as JSON.
+fn print_verbose(args: &Cli, value: &str) {
+    let rucksacks = match read_rucksacks(args, value) {
+        Some(rucksacks) => rucksacks,
+        None => return,
+    };
+    let report = report(&rucksacks, args.group_size);
 
-#[derive(Debug)]
-struct Compartment {
-    items: Vec<char>,
-}
-
-impl Compartment {
-    fn create(items: &str) -> Compartment {
-        let compartment =  Compartment { items: items.chars().collect()};
-        return compartment;
+    match args.format.format {
+        Some(Format::Json) => println!("{}", report.to_json()),
+        _ => println!("{}", report.to_text()),
     }
 }
 
-fn get_char_priority(value: &char) -> i32 {
-    let ascii_value = *value as u8;
-    match ascii_value  {
-         _ if ascii_value >= 65 && ascii_value <= 90 => {
-             return (ascii_value as i32) - 65 + 1 + 26
-         },
-         _ if ascii_value >= 97 && ascii_value <= 122 => {
-             return (ascii_value as i32) - 97 + 1
-
-         },
-         _ => return 0
+/// Scores `value` with the --compartments and --group-size given instead of
+/// the puzzle's.
+fn print_custom(args: &Cli, value: &str) {
+    let rucksacks = match read_rucksacks(args, value) {
+        Some(rucksacks) => rucksacks,
+        None => return,
+    };
+
+    println!("The sum of items found in every one of the {} compartments is {}", args.compartments, calculate_duplicate_priority_score(&rucksacks));
+    match find_badges(&rucksacks, args.group_size).checked_score() {
+        Ok(score) => println!("The sum of badges for groups of {} is {}", args.group_size, score),
+        Err(err) => println!("{}", err),
     }
 }
 
 #[derive(Parser)]
 struct Cli {
-    path: PathBuf,
-}
-
-
-#[test]
-fn calculates_sample_score(){
-    let sample = "vJrwpWtwJgWrhcsFMMfFFhFp\n
-    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n
-    PmmdzqPrVvPwwTWBwg\n
-    wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n
-    ttgJtRGJQctTZtZT\n
-    CrZsJsPPZsGzwwsLwLmpwMDw\n";
-
-    let rucksacks: Vec<RuckSack> = get_rucksacks(&sample);
-    let total_score: i32 = calculate_duplicate_priority_score(rucksacks);
-    assert_eq!(total_score, 157)
-}
-
-#[test]
-fn calculates_group_badge(){
-    let sample = "vJrwpWtwJgWrhcsFMMfFFhFp\n
-    jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\n
-    PmmdzqPrVvPwwTWBwg\n
-    wMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\n
-    ttgJtRGJQctTZtZT\n
-    CrZsJsPPZsGzwwsLwLmpwMDw\n";
-
-    let rucksacks = get_rucksacks(&sample);
-    let total_score: i32 = group_and_score_rucksacks(rucksacks);
-    assert_eq!(total_score, 70)
-}
-
-#[test]
-fn rucksack_creates_correct_compartments(){
-    let rucksack = RuckSack::create_with_two_compartments("jqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL");
-    match &rucksack.compartments[..] {
-        [first, second] => {
-            let first_str: String = first.items.iter().collect();
-            let second_str: String = second.items.iter().collect();
-
-            assert_eq!(first_str, "jqHRNqRjqzjGDLGL");  //jNqGzHDRL
-            assert_eq!(second_str, "rsFMfFZSrLrFZsSL"); //FrfMLsZ
-    
-        },
-        _ => {
-            panic!("This should never happen!")
+    #[command(flatten)]
+    input: InputArgs,
+
+    #[command(flatten)]
+    part: PartArgs,
+
+    #[command(flatten)]
+    parse: ParseArgs,
+
+    #[command(flatten)]
+    format: FormatArgs,
+
+    /// How many equal compartments each rucksack is split into. Only items
+    /// found in every compartment are scored
+    #[arg(long, default_value_t = COMPARTMENTS, value_parser = RangedU64ValueParser::<usize>::new().range(1..), conflicts_with = "part")]
+    compartments: usize,
+
+    /// How many rucksacks share a badge
+    #[arg(long, default_value_t = GROUP_SIZE, value_parser = RangedU64ValueParser::<usize>::new().range(1..), conflicts_with = "part")]
+    group_size: usize,
+
+    /// Show the items each rucksack and group shares, with their priorities.
+    /// Printed as JSON with --format json
+    #[arg(long, short, conflicts_with = "part")]
+    verbose: bool,
+}
+
+impl Cli {
+    /// Combinations clap cannot check on its own. The answer report only
+    /// covers the puzzle's compartments and group size, and the --verbose
+    /// report comes only as text or JSON.
+    fn conflict(&self) -> Option<String> {
+        let custom = self.compartments != COMPARTMENTS || self.group_size != GROUP_SIZE;
+        match self.format.format {
+            Some(Format::Csv) if self.verbose => Some("--verbose cannot be printed with --format csv, use text or json".to_string()),
+            Some(_) if custom && !self.verbose => Some("--format needs --verbose to report with --compartments or --group-size".to_string()),
+            _ => None,
         }
     }
 }
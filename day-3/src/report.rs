@@ -0,0 +1,204 @@
+use serde::Serialize;
+
+use crate::{find_badges, get_char_priority, RuckSack};
+
+/// What every rucksack and group scored and why, in place of printing while
+/// scoring.
+#[derive(Debug, Serialize)]
+pub struct Report {
+    pub rucksacks: Vec<RuckSackReport>,
+    pub groups: Vec<GroupReport>,
+    /// Rucksacks left over after the last full group, which are not scored.
+    pub leftover: usize,
+    /// The sum of every rucksack's score, part 1 for the puzzle's rucksacks.
+    pub duplicate_score: i32,
+    /// The sum of every group's score, part 2 for the puzzle's rucksacks.
+    pub badge_score: i32,
+}
+
+#[derive(Debug, Serialize)]
+pub struct RuckSackReport {
+    /// The line of the input the rucksack was read from.
+    pub line: usize,
+    pub items: String,
+    /// Every item found in more than one compartment, or in the only one.
+    /// Only those found in every compartment are scored.
+    pub shared: Vec<SharedItem>,
+    pub score: i32,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct SharedItem {
+    pub item: char,
+    pub priority: i32,
+    /// The compartments holding the item, counting from 1.
+    pub compartments: Vec<usize>,
+    /// Whether the item is in every compartment, so counts towards the score.
+    pub scored: bool,
+}
+
+#[derive(Debug, Serialize)]
+pub struct GroupReport {
+    /// The position of the group in the input, counting from 1.
+    pub group: usize,
+    /// The lines of the input the group's rucksacks were read from.
+    pub lines: Vec<usize>,
+    pub badges: Vec<Badge>,
+    pub score: i32,
+}
+
+#[derive(Debug, Serialize, PartialEq, Eq)]
+pub struct Badge {
+    pub item: char,
+    pub priority: i32,
+}
+
+/// Reports on every rucksack and on every full group of `group_size`.
+pub fn report(rucksacks: &[RuckSack], group_size: usize) -> Report {
+    let rucksack_reports: Vec<RuckSackReport> = rucksacks.iter().map(report_rucksack).collect();
+
+    let badges = find_badges(rucksacks, group_size);
+    let groups: Vec<GroupReport> = badges
+        .badges
+        .iter()
+        .enumerate()
+        .map(|(index, badge)| GroupReport {
+            group: index + 1,
+            lines: rucksacks[index * group_size..(index + 1) * group_size].iter().map(|rucksack| rucksack.line()).collect(),
+            badges: badge.items().map(|item| Badge { item, priority: get_char_priority(&item) }).collect(),
+            score: badge.priority_sum(),
+        })
+        .collect();
+
+    return Report {
+        duplicate_score: rucksack_reports.iter().map(|r| r.score).sum(),
+        badge_score: badges.score(),
+        rucksacks: rucksack_reports,
+        groups,
+        leftover: badges.leftover,
+    };
+}
+
+fn report_rucksack(rucksack: &RuckSack) -> RuckSackReport {
+    let shared = rucksack
+        .get_items()
+        .items()
+        .filter_map(|item| {
+            let compartments: Vec<usize> = rucksack
+                .compartments
+                .iter()
+                .enumerate()
+                .filter(|(_, compartment)| compartment.items.contains(item))
+                .map(|(index, _)| index + 1)
+                .collect();
+
+            let scored = compartments.len() == rucksack.compartments.len();
+            if compartments.len() < 2 && !scored {
+                return None;
+            }
+            return Some(SharedItem { item, priority: get_char_priority(&item), compartments, scored });
+        })
+        .collect();
+
+    return RuckSackReport { line: rucksack.line(), items: rucksack.contents.clone(), shared, score: rucksack.get_shared_items().priority_sum() };
+}
+
+impl Report {
+    /// One line per rucksack and per group, then the totals.
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+
+        for rucksack in &self.rucksacks {
+            let shared: Vec<String> = rucksack
+                .shared
+                .iter()
+                .map(|shared| {
+                    let compartments: Vec<String> = shared.compartments.iter().map(|c| c.to_string()).collect();
+                    let note = if shared.scored { "" } else { ", not scored" };
+                    return format!("{} (priority {}, compartments {}{})", shared.item, shared.priority, compartments.join(" and "), note);
+                })
+                .collect();
+            let shared = if shared.is_empty() { "nothing".to_string() } else { shared.join(", ") };
+            text += &format!("Rucksack on line {} {} shares {}, scoring {}\n", rucksack.line, rucksack.items, shared, rucksack.score);
+        }
+
+        for group in &self.groups {
+            let badges: Vec<String> = group.badges.iter().map(|badge| format!("{} (priority {})", badge.item, badge.priority)).collect();
+            let badges = if badges.is_empty() { "no badge".to_string() } else { badges.join(", ") };
+            let lines: Vec<String> = group.lines.iter().map(|line| line.to_string()).collect();
+            text += &format!("Group {} of the rucksacks on lines {} has {}, scoring {}\n", group.group, lines.join(", "), badges, group.score);
+        }
+
+        if self.leftover > 0 {
+            text += &format!("The last {} rucksack(s) do not make up a group and are not scored\n", self.leftover);
+        }
+        text += &format!("Duplicate score {}, badge score {}", self.duplicate_score, self.badge_score);
+        return text;
+    }
+
+    pub fn to_json(&self) -> String {
+        return serde_json::to_string_pretty(self).unwrap();
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{get_rucksacks, get_rucksacks_with, GROUP_SIZE};
+    use aoc_core::Recovery;
+
+    const SAMPLE: &str = "vJrwpWtwJgWrhcsFMMfFFhFp\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\nwMqvLMZHhHMvwLHjbvcjnnSBnvTQFn\nttgJtRGJQctTZtZT\nCrZsJsPPZsGzwwsLwLmpwMDw\n";
+
+    #[test]
+    fn reports_each_rucksack_and_group_of_the_sample() {
+        let rucksacks = get_rucksacks(SAMPLE, &mut Recovery::strict()).unwrap();
+        let report = report(&rucksacks, GROUP_SIZE);
+
+        assert_eq!(report.rucksacks[0].shared, vec![SharedItem { item: 'p', priority: 16, compartments: vec![1, 2], scored: true }]);
+        assert_eq!(report.groups[1].badges, vec![Badge { item: 'Z', priority: 52 }]);
+        assert_eq!(report.groups[1].lines, vec![4, 5, 6]);
+        assert_eq!((report.duplicate_score, report.badge_score, report.leftover), (157, 70, 0));
+    }
+
+    #[test]
+    fn marks_items_missing_from_a_compartment_as_not_scored() {
+        let rucksacks = get_rucksacks_with("abXaYc\n", 3, &mut Recovery::strict()).unwrap();
+        let report = report(&rucksacks, GROUP_SIZE);
+
+        assert_eq!(report.rucksacks[0].shared, vec![SharedItem { item: 'a', priority: 1, compartments: vec![1, 2], scored: false }]);
+        assert_eq!(report.leftover, 1);
+        assert_eq!(report.rucksacks[0].score, 0);
+        assert!(report.to_text().contains("not scored"));
+    }
+
+    #[test]
+    fn scores_only_what_it_marks_as_scored() {
+        for compartments in [1, 2, 3] {
+            let rucksacks = get_rucksacks_with("abXabY\n", compartments, &mut Recovery::strict()).unwrap();
+            let report = report(&rucksacks, GROUP_SIZE);
+            let scored: i32 = report.rucksacks[0].shared.iter().filter(|shared| shared.scored).map(|shared| shared.priority).sum();
+
+            assert_eq!(scored, report.rucksacks[0].score, "{} compartments", compartments);
+        }
+    }
+
+    #[test]
+    fn points_at_input_lines_past_blank_and_skipped_ones() {
+        let input = "vJrwpWtwJgWrhcsFMMfFFhFp\n\n12\njqHRNqRjqzjGDLGLrsFMfFZSrLrFZsSL\nPmmdzqPrVvPwwTWBwg\n";
+        let rucksacks = get_rucksacks(input, &mut Recovery::lenient()).unwrap();
+        let report = report(&rucksacks, GROUP_SIZE);
+
+        assert_eq!(report.rucksacks.iter().map(|r| r.line).collect::<Vec<usize>>(), vec![1, 4, 5]);
+        assert_eq!(report.groups[0].lines, vec![1, 4, 5]);
+        assert!(report.to_text().starts_with("Rucksack on line 1 "));
+    }
+
+    #[test]
+    fn renders_as_json() {
+        let rucksacks = get_rucksacks(SAMPLE, &mut Recovery::strict()).unwrap();
+        let json: serde_json::Value = serde_json::from_str(&report(&rucksacks, GROUP_SIZE).to_json()).unwrap();
+
+        assert_eq!(json["rucksacks"][0]["shared"][0]["item"], "p");
+        assert_eq!(json["badge_score"], 70);
+    }
+}
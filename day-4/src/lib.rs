@@ -0,0 +1,178 @@
+use std::{collections::{HashSet}, ops::Range };
+use std::path::{Path, PathBuf};
+use aoc_core::{Answer, Error, ParseError, Recovery, Solution};
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Vec<Pair<ZoneRange>>;
+
+    const DAY: u8 = 4;
+
+    fn default_input() -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+    }
+
+    fn parse_with(input: &str, recovery: &mut Recovery) -> Result<Vec<Pair<ZoneRange>>, ParseError> {
+        return get_assignments(input, recovery);
+    }
+
+    fn part1(assignments: &Vec<Pair<ZoneRange>>) -> Result<Answer, Error> {
+        return Ok(get_fully_contained_assignment_count(assignments).into());
+    }
+
+    fn part2(assignments: &Vec<Pair<ZoneRange>>) -> Result<Answer, Error> {
+        return Ok(get_overlap_count(assignments).into());
+    }
+}
+
+pub fn get_assignments(value: &str, recovery: &mut Recovery) -> Result<Vec<Pair<ZoneRange>>, ParseError> {
+    return value
+        .split("\n")
+        .map(|line| line.trim())
+        .filter(|line| !line.is_empty())
+        .map(|line| recovery.recover(Pair::parse_zone_ranges(value, line)))
+        .filter_map(|pair| pair.transpose())
+        .collect();
+}
+
+pub fn get_fully_contained_assignment_count(assignments: &[Pair<ZoneRange>]) -> i32{
+    return assignments
+    .iter()
+    .fold(0, |count, p| if p.zone_fully_contained_by_other() { count + 1 } else { count } );
+}
+
+pub fn get_overlap_count(assignments: &[Pair<ZoneRange>]) -> i32{
+    return assignments
+    .iter()
+    .fold(0, | count, p | match p.zones_overlap() { 
+        (true, _) => { return count + 1 },
+        _ => return count,
+    });
+}
+
+#[derive(Clone, Copy)]
+pub struct ZoneRange {
+    start: i32,
+    end: i32,
+}
+
+pub struct Pair<T>{
+    x: T,
+    y: T,
+}
+
+impl Pair<ZoneRange> {
+    /// Parses `value`, which must be a slice of `source` so that errors can
+    /// point at it.
+    fn parse_zone_ranges(source: &str, value: &str) -> Result<Pair<ZoneRange>, ParseError>{
+        let zone_ranges_str: Vec<&str> = value.split(",").collect();
+
+        match zone_ranges_str[..] {
+            [first, second] => {
+                return Ok(Pair { x: ZoneRange::parse(source, first)?, y: ZoneRange::parse(source, second)? });
+            },
+            _ => return Err(ParseError::spanning(source, value, "Invalid zone range provided, expected two ranges separated by a comma"))
+        }
+    }
+
+    fn zone_fully_contained_by_other(&self) -> bool {
+        let x: HashSet<i32> = self.x.to_range().collect();
+        let y: HashSet<i32> = self.y.to_range().collect();
+
+        return x.is_superset(&y) || y.is_superset(&x);
+    }
+
+    fn zones_overlap(&self) -> (bool, Vec<i32>)  {
+        let x: HashSet<i32> = self.x.to_range().collect();
+        let y: HashSet<i32> = self.y.to_range().collect();
+
+        let intersect: Vec<i32> = x.intersection(&y).copied().collect();
+        return (!intersect.is_empty(), intersect);
+    }
+}
+
+
+impl ZoneRange {
+    fn parse(source: &str, zones: &str) -> Result<ZoneRange, ParseError> {
+        let segments: Vec<&str> = zones.split("-").collect();
+        match segments[..] {
+            [start, end] => {
+                let converted = (start.parse::<i32>(), end.parse::<i32>());
+                
+                match converted {
+                    (Ok(start_converted), Ok(end_converted)) => {
+                        return Ok(ZoneRange { start: start_converted, end: end_converted })
+                    },
+                    (Err(_), _) => { Err(ParseError::spanning(source, start, format!("Failed to parse range {}", zones)))},
+                    (_, Err(_)) => { Err(ParseError::spanning(source, end, format!("Failed to parse range {}", zones)))}
+                }
+
+                
+            },
+            _ => { return Err(ParseError::spanning(source, zones, format!("Unable to parse range {} as it didn't appear to be an actual range", zones))) }
+        }
+    }
+    
+    fn to_range(self) -> Range<i32> {
+        return self.start..self.end + 1;
+    }
+
+}
+
+
+#[test]
+fn calculates_fully_contained_count(){
+    let sample = "2-4,6-8\n
+    2-3,4-5\n
+    5-7,7-9\n
+    2-8,3-7\n
+    6-6,4-6\n
+    2-6,4-8";
+
+    let assignments = get_assignments(sample, &mut Recovery::strict()).unwrap();
+    let count = get_fully_contained_assignment_count(&assignments);
+
+    assert_eq!(count, 2)
+}
+
+#[test]
+fn calculates_overlap_count(){
+    let sample = "2-4,6-8\n
+    2-3,4-5\n
+    5-7,7-9\n
+    2-8,3-7\n
+    6-6,4-6\n
+    2-6,4-8";
+
+    let assignments = get_assignments(sample, &mut Recovery::strict()).unwrap();
+    let count = get_overlap_count(&assignments);
+
+    assert_eq!(count, 4)
+}
+
+#[test]
+fn reports_unreadable_section_number(){
+    let err = get_assignments("2-4,6-8\n2-3,4-x5", &mut Recovery::strict()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.column, span.text.as_str()), (2, 7, "x5"));
+}
+
+#[test]
+fn skips_unreadable_assignment_when_lenient(){
+    let mut recovery = Recovery::lenient();
+    let assignments = get_assignments("2-8,3-7\n2-3,4-x5", &mut recovery).unwrap();
+
+    assert_eq!(get_fully_contained_assignment_count(&assignments), 1);
+    assert_eq!(recovery.skipped().len(), 1);
+}
+
+#[test]
+fn matches_recorded_answers(){
+    let input = std::fs::read_to_string(Puzzle::default_input()).unwrap();
+
+    for part in aoc_core::Part::ALL {
+        aoc_core::answers::assert_recorded::<Puzzle>(part, &input);
+    }
+}
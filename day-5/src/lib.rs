@@ -0,0 +1,339 @@
+extern crate regex;
+use std::collections::{VecDeque};
+use std::path::{Path, PathBuf};
+use aoc_core::{Answer, Error, ParseError, Recovery, Solution};
+use regex::{Regex, Captures};
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = Rearrangement;
+
+    const DAY: u8 = 5;
+
+    fn default_input() -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+    }
+
+    fn parse_with(input: &str, recovery: &mut Recovery) -> Result<Rearrangement, ParseError> {
+        return Rearrangement::parse(input, recovery);
+    }
+
+    fn part1(rearrangement: &Rearrangement) -> Result<Answer, Error> {
+        let mut arranged_stacks = rearrangement.stacks.get_arranged_stacks(&rearrangement.moves, CrateMover::Model9000).map_err(Error::Solve)?;
+        return Ok(arranged_stacks.get_top_crates_str().into());
+    }
+
+    fn part2(rearrangement: &Rearrangement) -> Result<Answer, Error> {
+        let mut arranged_stacks = rearrangement.stacks.get_arranged_stacks(&rearrangement.moves, CrateMover::Model9001).map_err(Error::Solve)?;
+        return Ok(arranged_stacks.get_top_crates_str().into());
+    }
+}
+
+/// The crane doing the rearranging. The 9000 lifts one crate at a time, so
+/// crates moved together end up in reverse order. The 9001 lifts them all at
+/// once and keeps their order.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum CrateMover {
+    Model9000,
+    Model9001,
+}
+
+pub struct Rearrangement {
+    pub stacks: Stacks,
+    pub moves: Moves,
+}
+
+impl Rearrangement {
+    /// Parses the stack drawing and the moves. A bad crate in the drawing
+    /// always fails, since skipping it would shift the crates above it; bad
+    /// moves are handed to `recovery`.
+    pub fn parse(content: &str, recovery: &mut Recovery) -> Result<Rearrangement, ParseError>{
+        let split_content: Vec<&str> = content.split("\n\n").collect();
+
+        match split_content[..] {
+            [stack_content, move_content] => {
+                let stacks = Stacks::parse(content, stack_content)?;
+                let moves = Moves::parse(content, move_content, recovery)?;
+
+                return Ok(Rearrangement { stacks, moves })
+            },
+            _ => Err(ParseError::new("Invalid puzzle content provided, expected the stacks and the moves separated by a blank line"))
+        }
+    }
+
+}
+
+pub struct Moves {
+    items: Vec<Move>,
+}
+
+impl Moves {
+    fn parse(source: &str, value: &str, recovery: &mut Recovery) -> Result<Moves, ParseError> {
+        let items = value
+            .split("\n")
+            .filter(|f| !f.trim().is_empty())
+            .map(|f| recovery.recover(Move::parse(source, f)))
+            .filter_map(|f| f.transpose())
+            .collect::<Result<Vec<Move>, ParseError>>()?;
+
+        return Ok(Moves { items });
+    }
+}
+
+#[derive(Clone, Copy)]
+pub struct Move {
+    source: usize,
+    destination: usize,
+    number: usize,
+}
+
+impl Move {
+    /// Parses `value`, which must be a slice of `source` so that errors can
+    /// point at it.
+    fn parse(source: &str, value: &str) -> Result<Move, ParseError> {
+
+        let regex = Regex::new(r"move\s+(\d+)\s+from\s+(\d+)\s+to\s+(\d+)").unwrap();
+
+        match regex.captures_iter(value).collect::<Vec<Captures>>().first() {
+            Some(capture) => {
+                let number = Move::parse_number(source, capture, 1, 0)?;
+                let from = Move::parse_number(source, capture, 2, 1)?;
+                let to = Move::parse_number(source, capture, 3, 1)?;
+
+                return Ok(Move { number, source: from, destination: to })
+            },
+            None => return Err(ParseError::spanning(source, value.trim(), format!("Could not parse move {}", value.trim())))
+        }
+    }
+
+    fn parse_number(source: &str, capture: &Captures, group: usize, minimum: usize) -> Result<usize, ParseError> {
+        let raw = capture.get(group).unwrap().as_str();
+
+        match raw.parse::<usize>() {
+            Ok(number) if number >= minimum => Ok(number),
+            Ok(_) => Err(ParseError::spanning(source, raw, format!("Stack numbers start at {}", minimum))),
+            Err(err) => Err(ParseError::spanning(source, raw, format!("Could not parse number {}: {}", raw, err))),
+        }
+    }
+}
+
+pub struct Stacks {
+    items: Vec<VecDeque<char>>
+}
+
+impl Stacks {
+    fn parse(source: &str, content: &str) -> Result<Stacks, ParseError> {
+        let mut crates: Vec<&str> = content.split("\n").collect::<Vec<&str>>();
+
+        match crates.pop() { 
+            Some(indexes) => {
+                crates.reverse();
+                let mut stacks: Vec<VecDeque<char>> = indexes.split_whitespace().map(|_| VecDeque::new()).collect();
+        
+                for &crate_line in crates.iter() {
+                    // Every crate takes up four characters: "[A] ".
+                    let starts: Vec<usize> = crate_line.char_indices().map(|(i, _)| i).step_by(4).collect();
+
+                    for (index, &start) in starts.iter().enumerate() {
+                        let end = starts.get(index + 1).copied().unwrap_or(crate_line.len());
+                        let crate_identifier = crate_line[start..end].trim();
+
+                        if crate_identifier.is_empty() {
+                            continue;
+                        }
+
+                        let identifier: Vec<char> = crate_identifier.chars().collect();
+                        match identifier[..] {
+                            ['[', id, ']'] => match stacks.get_mut(index) {
+                                Some(stack) => stack.push_back(id),
+                                None => return Err(ParseError::spanning(source, crate_identifier, format!("Crate is above stack {} but there are only {} stacks", index + 1, stacks.len())))
+                            },
+                            _ => return Err(ParseError::spanning(source, crate_identifier, format!("Expected a crate like [A] but found {}", crate_identifier)))
+                        }
+                    }
+                }
+        
+                return Ok(Stacks { items: stacks.into_iter().collect::<Vec<VecDeque<char>>>() });
+            }
+            None => Err(ParseError::new("Provided input did not contain any elements"))
+        }
+
+    }
+
+    pub fn get_arranged_stacks(&self, moves: &Moves, crane: CrateMover) -> Result<Stacks, String> {
+        
+        let mut items: Vec<VecDeque<char>> = self.items.iter().map(|f|f.iter().cloned().collect::<VecDeque<char>>()).collect();
+        
+        for r#move in moves.items.iter(){
+            let mut drained: VecDeque<char> = VecDeque::new();
+
+            match items.get_mut(r#move.source - 1){
+                Some(source) => {
+                    for _ in 0..r#move.number {
+                        match source.pop_back() {
+                            Some(item) => match crane {
+                                CrateMover::Model9000 => drained.push_back(item),
+                                CrateMover::Model9001 => drained.push_front(item),
+                            },
+                            None => {
+                                return Err(String::from("Number of moves exceeded stack"))
+                            }
+                        }
+                    }
+                },
+                None => {
+                    return Err(format!("Invalid source index provided for move: move {} from {} to {}", r#move.number, r#move.source, r#move.destination ));
+                }
+            }
+
+            match items.get_mut(r#move.destination -1){
+                Some(destination) => {
+                    destination.append(&mut drained);
+                },
+                None => {
+                    return Err(format!("Invalid destination index provided for move: move {} from {} to {}", r#move.number, r#move.source, r#move.destination ));
+                }
+            }
+        }
+          
+        return Ok(Stacks { items });
+    }
+
+    pub fn get_top_crates(&mut self) -> Vec<char> {
+        return self.items.iter().flat_map(|f| {
+            return f.back().cloned();
+        }).collect();
+    }
+
+    pub fn get_top_crates_str(&mut self) -> String {
+        return self.get_top_crates().iter().map(|c|c.to_string()).collect::<Vec<String>>().join("");
+    }
+}
+
+
+#[cfg(test)]
+const SAMPLE: &str = "    [D]    
+[N] [C]    
+[Z] [M] [P]
+ 1   2   3 
+
+move 1 from 2 to 1
+move 3 from 1 to 3
+move 2 from 2 to 1
+move 1 from 1 to 2";
+
+
+#[test]
+fn correctly_arranges_stacks_one_crate_at_a_time(){
+    let puzzle: Rearrangement = Rearrangement::parse(SAMPLE, &mut Recovery::strict()).unwrap();
+    match puzzle.stacks.get_arranged_stacks(&puzzle.moves, CrateMover::Model9000) {
+        Ok(mut arranged_stacks) => {
+            assert_eq!(arranged_stacks.get_top_crates_str(), "CMZ");
+        },
+        Err(err) => panic!("{}", err)
+    }
+}
+
+#[test]
+fn correctly_arranges_stacks_based_on_moves(){
+    let puzzle: Rearrangement = Rearrangement::parse(SAMPLE, &mut Recovery::strict()).unwrap();
+    match puzzle.stacks.get_arranged_stacks(&puzzle.moves, CrateMover::Model9001) {
+        Ok(mut arranged_stacks) => {
+            assert_eq!(arranged_stacks.get_top_crates_str(), "MCD");
+        },
+        Err(err) => panic!("{}", err)
+    }
+}   
+
+#[test]
+fn parses_puzzle_with_correct_indexes(){
+    let puzzle = Rearrangement::parse(SAMPLE, &mut Recovery::strict()).unwrap();
+    assert_eq!(puzzle.stacks.items.iter().len(), 3)
+}
+
+#[test]
+fn parses_puzzle_with_correct_stacks(){
+    let puzzle = Rearrangement::parse(SAMPLE, &mut Recovery::strict()).unwrap();
+    let items = &puzzle.stacks.items;
+    match (items.first(), items.get(1), items.get(2)) {
+        (Some(first_stack), Some(second_stack), Some(third_stack)) => {
+            assert_stack_values_eq(first_stack, &[&'Z', &'N']);
+            assert_stack_values_eq(second_stack, &[&'M', &'C', &'D']);
+            assert_stack_values_eq(third_stack, &[&'P']);
+        } 
+        _ => { panic!("Invalid number of stacks.")}
+    }
+}
+
+#[test]
+fn parses_moves(){
+    let puzzle = Rearrangement::parse(SAMPLE, &mut Recovery::strict()).unwrap();
+    
+    let expected = vec![
+        Move{ number:1, source: 2, destination: 1}, 
+        Move{ number: 3, source: 1, destination: 3 }, 
+        Move{ number: 2, source: 2, destination: 1}, 
+        Move{ number: 1, source: 1, destination: 2 }
+    ];
+
+    assert_moves_eq(expected, puzzle.moves.items);
+}
+
+#[cfg(test)]
+fn assert_moves_eq(expected: Vec<Move>, received: Vec<Move>){
+    assert_eq!(expected.len(), received.len(), "The number of moves do not match");
+
+    expected.iter().zip(received.iter()).for_each(|(left, right)| assert_move_eq(left, right))
+}
+
+
+#[cfg(test)]
+fn assert_move_eq(expected: &Move, received: &Move){
+    assert_eq!(received.number, expected.number);
+    assert_eq!(received.source, expected.source);
+    assert_eq!(received.destination, expected.destination);
+}
+
+#[cfg(test)]
+fn assert_stack_values_eq(stack: &VecDeque<char>, expected: &[&char]) {
+    let converted: Vec<&char> = stack.iter().collect();
+    assert_eq!(&converted[..], expected);
+}
+
+
+#[test]
+fn reports_unreadable_move_with_its_line(){
+    let input = SAMPLE.replace("move 2 from 2 to 1", "move 2 frm 2 to 1");
+    let err = Rearrangement::parse(&input, &mut Recovery::strict()).err().unwrap();
+    let span = err.span.unwrap();
+
+    assert_eq!((span.line, span.column, span.text.as_str()), (8, 1, "move 2 frm 2 to 1"));
+}
+
+#[test]
+fn reports_moves_from_stack_zero(){
+    let input = SAMPLE.replace("move 1 from 1 to 2", "move 1 from 0 to 2");
+    let err = Rearrangement::parse(&input, &mut Recovery::strict()).err().unwrap();
+
+    assert_eq!(err.span.unwrap().line, 9);
+}
+
+#[test]
+fn skips_unreadable_move_when_lenient(){
+    let input = SAMPLE.replace("move 2 from 2 to 1", "move 2 frm 2 to 1");
+    let mut recovery = Recovery::lenient();
+    let puzzle = Rearrangement::parse(&input, &mut recovery).unwrap();
+
+    assert_eq!(puzzle.moves.items.len(), 3);
+    assert_eq!(recovery.skipped()[0].span.as_ref().unwrap().line, 8);
+}
+
+#[test]
+fn matches_recorded_answers(){
+    let input = std::fs::read_to_string(Puzzle::default_input()).unwrap();
+
+    for part in aoc_core::Part::ALL {
+        aoc_core::answers::assert_recorded::<Puzzle>(part, &input);
+    }
+}
@@ -0,0 +1,121 @@
+use std::path::{Path, PathBuf};
+use aoc_core::{Answer, Error, ParseError, Recovery, Solution};
+
+/// The puzzle input built into the binary by the `embedded-input` feature.
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("../input"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+
+pub struct Puzzle;
+
+
+impl Puzzle {
+    pub fn solve_part_1(signal: &[u8]) -> Option<usize> {
+        const WINDOW_SIZE: usize = 4;
+
+        return signal.windows(WINDOW_SIZE).position(|x| {
+            (x[0] != x[1] && x[0] != x[2] && x[0] != x[3])
+            && (x[1] != x[2] && x[1] != x[3])
+            && (x[2] != x[3])
+        }).map(|position| position + WINDOW_SIZE);
+    }
+
+    pub fn solve_part_2(signal: &[u8]) -> Option<usize> {
+        const WINDOW_SIZE: usize = 14;
+
+        return signal.windows(WINDOW_SIZE).position(|x| {
+            let mut seen = [false; 52];
+            
+            for &e in x {
+                let index: usize = (if (97..=122).contains(&e) { e - 97 } else { e - 65 + 26 }) as usize;
+                
+                if seen[index] {
+                    return false;
+                }
+
+                seen[index] = true;
+            }
+
+            return true;
+
+        }).map(|position| position + WINDOW_SIZE);
+
+    }
+}
+
+impl Solution for Puzzle {
+    type Parsed = Vec<u8>;
+
+    const DAY: u8 = 6;
+
+    fn default_input() -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+    }
+
+    /// The datastream is a single line and dropping a character would move
+    /// every marker after it, so a bad character always fails the parse.
+    fn parse_with(input: &str, _recovery: &mut Recovery) -> Result<Vec<u8>, ParseError> {
+        let signal = input.trim();
+
+        match signal.char_indices().find(|(_, c)| !c.is_ascii_alphabetic()) {
+            Some((index, c)) => Err(ParseError::spanning(input, &signal[index..index + c.len_utf8()], format!("Unexpected character {:?} in datastream", c))),
+            None => Ok(signal.as_bytes().to_vec()),
+        }
+    }
+
+    fn part1(signal: &Vec<u8>) -> Result<Answer, Error> {
+        return Puzzle::solve_part_1(signal)
+            .map(Answer::from)
+            .ok_or_else(|| Error::Solve(String::from("No start-of-packet marker found")));
+    }
+
+    fn part2(signal: &Vec<u8>) -> Result<Answer, Error> {
+        return Puzzle::solve_part_2(signal)
+            .map(Answer::from)
+            .ok_or_else(|| Error::Solve(String::from("No start-of-message marker found")));
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answers::assert_recorded;
+    use aoc_core::Part;
+
+    const INPUT: &str = include_str!("../input");
+
+
+    #[test]
+    fn test_part1_sample() {
+        assert_eq!( Puzzle::solve(Part::One, "bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), Answer::from(5));
+        assert_eq!( Puzzle::solve(Part::One, "nppdvjthqldpwncqszvftbrmjlhg").unwrap(), Answer::from(6));
+        assert_eq!( Puzzle::solve(Part::One, "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), Answer::from(10));
+        assert_eq!( Puzzle::solve(Part::One, "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), Answer::from(11));
+    }
+
+    #[test]
+    fn test_part1_output() {
+        assert_recorded::<Puzzle>(Part::One, INPUT);
+    }
+    
+
+    #[test]
+    fn test_part2_sample() {
+        assert_eq!( Puzzle::solve(Part::Two, "mjqjpqmgbljsphdztnvjfqwrcgsmlb").unwrap(), Answer::from(19));
+        assert_eq!( Puzzle::solve(Part::Two, "bvwbjplbgvbhsrlpgdmjqwftvncz").unwrap(), Answer::from(23));
+        assert_eq!( Puzzle::solve(Part::Two, "nppdvjthqldpwncqszvftbrmjlhg").unwrap(), Answer::from(23));
+        assert_eq!( Puzzle::solve(Part::Two, "nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg").unwrap(), Answer::from(29));
+        assert_eq!( Puzzle::solve(Part::Two, "zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw").unwrap(), Answer::from(26));
+    }
+
+    #[test]
+    fn test_part2_output() {
+        assert_recorded::<Puzzle>(Part::Two, INPUT);
+    }
+
+
+}
+
+
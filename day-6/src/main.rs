@@ -1,106 +1,39 @@
-#![feature(test)]
+use aoc_core::answer::format_answer;
+use aoc_core::cli::{print_report, EmbeddedInputArgs, FormatArgs, ParseArgs};
+use aoc_core::{Error, Part, Solution};
 use clap::Parser;
-
-const INPUT: &str = include_str!("../input");
-
+use day_6::{Puzzle, EMBEDDED_INPUT};
 
 fn main() {
     let args = Cli::parse();
 
-    match args {
-        Cli { part: 1 } => println!("Answer for part 1 is {}", Puzzle::solve_part_1(INPUT)),
-        Cli { part: 2 } => println!("Answer for part 2 is {}", Puzzle::solve_part_2(INPUT)),
-        _ => panic!("Unknown part. Can either be 1 or 2")
-    }
-
-}
+    let input = match args.input.read(EMBEDDED_INPUT) {
+        Ok(input) => input,
+        Err(err) => return println!("{}", err),
+    };
 
-
-#[derive(Parser)]
-struct Cli {
-    part: usize,
-}
-
-
-struct Puzzle;
-
-
-impl Puzzle {
-    fn solve_part_1(input: &str) -> usize {
-        const WINDOW_SIZE: usize = 4;
-
-        return input.as_bytes().windows(WINDOW_SIZE).position(|x| {
-            (x[0] != x[1] && x[0] != x[2] && x[0] != x[3])
-            && (x[1] != x[2] && x[1] != x[3])
-            && (x[2] != x[3])
-        }).unwrap() + WINDOW_SIZE;
+    if let Some(format) = args.format.format {
+        return print_report::<Puzzle>(&input, &[args.part], args.parse.mode(), format);
     }
 
-    fn solve_part_2(input: &str) -> usize {
-        const WINDOW_SIZE: usize = 14;
-
-        return input.as_bytes().windows(WINDOW_SIZE).position(|x| {
-            let mut seen = [false; 52];
-            
-            for &e in x {
-                let index: usize = (if e >= 97 && e <= 122 { e - 97 } else { e - 65 + 26 }) as usize;
-                
-                if seen[index] {
-                    return false;
-                }
-
-                seen[index] = true;
-            }
-
-            return true;
-
-        }).unwrap() + WINDOW_SIZE;
-
+    match args.parse.parse_input::<Puzzle>(&input).map_err(Error::from).and_then(|parsed| Puzzle::solve_parsed(args.part, &parsed)) {
+        Ok(answer) => println!("{}", format_answer(args.part, &answer)),
+        Err(err) => println!("{}", err.render(&input)),
     }
-}
-
-
-#[cfg(test)]
-mod tests {
-    use super::*;
-    extern crate test;
 
-    use test::Bencher;
-
-
-    #[test]
-    fn test_part1_sample() {
-        assert_eq!( Puzzle::solve_part_1("bvwbjplbgvbhsrlpgdmjqwftvncz"), 5);
-        assert_eq!( Puzzle::solve_part_1("nppdvjthqldpwncqszvftbrmjlhg"), 6);
-        assert_eq!( Puzzle::solve_part_1("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 10);
-        assert_eq!( Puzzle::solve_part_1("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 11);
-    }
+}
 
-    #[test]
-    fn test_part1_output() {
-        let output = Puzzle::solve_part_1(INPUT);
-        assert!(output > 0);
-    }
-    
-    #[bench]
-    fn bench_part1(b: &mut Bencher) {
-        b.iter(|| Puzzle::solve_part_1(INPUT));
-    }
+#[derive(Parser)]
+struct Cli {
+    /// Which part to solve, either 1 or 2
+    part: Part,
 
-    #[test]
-    fn test_part2_sample() {
-        assert_eq!( Puzzle::solve_part_2("mjqjpqmgbljsphdztnvjfqwrcgsmlb"), 19);
-        assert_eq!( Puzzle::solve_part_2("bvwbjplbgvbhsrlpgdmjqwftvncz"), 23);
-        assert_eq!( Puzzle::solve_part_2("nppdvjthqldpwncqszvftbrmjlhg"), 23);
-        assert_eq!( Puzzle::solve_part_2("nznrnfrfntjfmvfwmzdfjlvtqnbhcprsg"), 29);
-        assert_eq!( Puzzle::solve_part_2("zcfzfwzzqfrljwzlrfnpqdbhtmscgvjw"), 26);
-    }
+    #[command(flatten)]
+    input: EmbeddedInputArgs,
 
-    #[bench]
-    fn bench_part2(b: &mut Bencher) {
-        b.iter(|| Puzzle::solve_part_2(INPUT));
-    }
+    #[command(flatten)]
+    parse: ParseArgs,
 
+    #[command(flatten)]
+    format: FormatArgs,
 }
-
-
@@ -0,0 +1,194 @@
+use std::path::{Path, PathBuf};
+use aoc_core::{Answer, Error, ParseError, Recovery, Solution};
+use std::collections::{ HashMap };
+
+/// The puzzle input built into the binary by the `embedded-input` feature.
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("../input"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+/// The total size of every directory, keyed by its path from the root.
+pub type DirectorySizes = HashMap<Vec<String>, i32>;
+
+pub struct Puzzle;
+
+impl Puzzle {
+    pub fn solve_part_1(sizes: &DirectorySizes) -> i32 {
+        const MAX_SIZE_INCLUSIVE: i32 = 100000;
+        return sizes.values().filter(|&&x| x <= MAX_SIZE_INCLUSIVE).sum();
+    }
+
+    pub fn solve_part_2(sizes: &DirectorySizes) -> Result<i32, Error> {
+        const TOTAL_DISK_SPACE: i32 =  70000000;
+        const REQUIRED_UNUSED_SPACE: i32 = 30000000;
+        let required_space = TOTAL_DISK_SPACE - REQUIRED_UNUSED_SPACE;
+
+        let total_used_space = sizes
+            .get(&vec![String::from("/")])
+            .ok_or_else(|| Error::Solve(String::from("The terminal output never changed into the root directory")))?;
+    
+        let minimum_required_space = total_used_space - required_space;
+        return sizes
+            .values()
+            .filter(|&&v| v >= minimum_required_space)
+            .min()
+            .copied()
+            .ok_or_else(|| Error::Solve(String::from("No directory is large enough to free up the required space")));
+    }
+
+    fn get_sizes(input: &str, recovery: &mut Recovery) -> Result<DirectorySizes, ParseError> {
+        let mut cwd: Vec<String> = Vec::new();
+        let mut sizes: DirectorySizes = HashMap::new();
+
+        for (index, line) in input.lines().enumerate() {
+            let parsed = match recovery.recover(Line::parse(line).map_err(|err| err.on_line(index + 1)))? {
+                Some(parsed) => parsed,
+                None => continue,
+            };
+
+            match parsed {
+                Line::ChangeDirectory(path)  => {
+                    if path == ".." {
+                        if cwd.pop().is_none() {
+                            recovery.recover::<()>(Err(ParseError::spanning(line, line.trim(), "Cannot change to the parent of the root directory").on_line(index + 1)))?;
+                        }
+                    } else {
+                        cwd.push(path.clone());
+                        sizes.insert(cwd.clone(), 0);
+                    }
+                },
+                Line::File(_, size) => {
+                    for i in (1..cwd.len() + 1).rev() {
+                        *sizes.get_mut(&cwd[0..i]).unwrap() += size;
+                    }
+                },
+                _ => {}, //Line::Directory
+            }
+        }
+
+        return Ok(sizes);
+    }
+}
+
+#[allow(dead_code)]
+enum Line {
+    ListDirectory(),
+    ChangeDirectory(String),
+    Directory(String),
+    File(String, i32),
+}
+
+impl Line {
+    fn parse(input: &str) -> Result<Line, ParseError> {
+        let segments: Vec<&str> = input.split_whitespace().map(|f|f.trim()).collect();
+
+        match (segments.first(), segments.get(1)){
+            (Some(&first), Some(&second)) => {
+
+                match first {
+                    "$" =>  {
+                        match second { 
+                            "cd" => {
+                                match segments.get(2) {
+                                    Some(&path) => {
+                                        return Ok(Line::ChangeDirectory(String::from(path)));
+                                    },
+                                    None => { return Err(ParseError::spanning(input, second, "Expected a path for change directory but there was none.")) }
+                                }
+                            },
+                            "ls" =>  Ok(Line::ListDirectory()),
+                            _ => return Err(ParseError::spanning(input, second, "Unknown command provided"))
+                        }
+                    }
+                    "dir" => {
+                        return Ok(Line::Directory(String::from(second)))
+                    },
+                    _ => {
+                        match first.parse::<i32>() {
+                            Ok(size) => return Ok(Line::File(String::from(second), size)),
+                            Err(_) => return Err(ParseError::spanning(input, first, "Expected a file size, `dir` or a `$` command")),
+                        }
+                    }
+                }
+            },
+            _ => return Err(ParseError::spanning(input, input.trim(), "Unknown input line provided")),
+        }
+
+    }
+}
+
+impl Solution for Puzzle {
+    type Parsed = DirectorySizes;
+
+    const DAY: u8 = 7;
+
+    fn default_input() -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+    }
+
+    fn parse_with(input: &str, recovery: &mut Recovery) -> Result<DirectorySizes, ParseError> {
+        return Puzzle::get_sizes(input, recovery);
+    }
+
+    fn part1(sizes: &DirectorySizes) -> Result<Answer, Error> {
+        return Ok(Puzzle::solve_part_1(sizes).into());
+    }
+
+    fn part2(sizes: &DirectorySizes) -> Result<Answer, Error> {
+        return Ok(Puzzle::solve_part_2(sizes)?.into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answers::assert_recorded;
+    use aoc_core::Part;
+
+    const INPUT: &str = include_str!("../input");
+
+    const SAMPLE: &str = include_str!("../sample");
+
+    #[test]
+    fn test_part1_sample() {
+        assert_eq!( Puzzle::solve(Part::One, SAMPLE).unwrap(), Answer::from(95437));
+    }
+
+    #[test]
+    fn test_part1_output() {
+        assert_recorded::<Puzzle>(Part::One, INPUT);
+    }
+
+
+
+    #[test]
+    fn test_part2_sample() {
+        assert_eq!( Puzzle::solve(Part::Two, SAMPLE).unwrap(), Answer::from(24933642));
+    }
+
+    #[test]
+    fn test_part2_output() {
+        assert_recorded::<Puzzle>(Part::Two, INPUT);
+    }
+
+    #[test]
+    fn reports_unknown_command_with_its_position() {
+        let err = Puzzle::parse("$ cd /\n$ ls\n$ rm a.txt").err().unwrap();
+        let span = err.span.unwrap();
+
+        assert_eq!((span.line, span.column, span.text.as_str()), (3, 3, "rm"));
+    }
+
+    #[test]
+    fn skips_unknown_command_when_lenient() {
+        let mut recovery = Recovery::lenient();
+        let sizes = Puzzle::parse_with("$ cd /\n$ rm a.txt\n10 b.txt", &mut recovery).unwrap();
+
+        assert_eq!(sizes[&vec![String::from("/")]], 10);
+        assert_eq!(recovery.skipped().len(), 1);
+    }
+
+}
+
+
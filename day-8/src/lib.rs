@@ -0,0 +1,286 @@
+
+use std::cell::{RefCell};
+use std::collections::{HashMap};
+use std::hash::{Hash};
+use std::iter;
+use std::rc::{ Rc, Weak };
+use std::path::{Path, PathBuf};
+use aoc_core::{Answer, Error, ParseError, Recovery, Solution};
+
+/// The puzzle input built into the binary by the `embedded-input` feature.
+#[cfg(feature = "embedded-input")]
+pub const EMBEDDED_INPUT: Option<&str> = Some(include_str!("../input"));
+#[cfg(not(feature = "embedded-input"))]
+pub const EMBEDDED_INPUT: Option<&str> = None;
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub enum Direction {
+    Above,
+    Right,
+    Below,
+    Left
+}
+
+#[derive(Debug, Eq, PartialEq, Hash, Copy, Clone)]
+pub struct Tree {
+    height: u8,
+}
+
+struct ForestGridTree {
+    height: u8,
+    connections: HashMap<Direction, Weak<RefCell<ForestGridTree>>>,
+}
+
+impl ForestGridTree {
+    fn new(height: u8) -> Self {
+        return ForestGridTree { height, connections: HashMap::new() }
+    }
+
+    fn add_connection(&mut self, direction: Direction, tree: Weak<RefCell<ForestGridTree>>) {
+        self.connections.insert(direction, tree);
+    }
+}
+
+pub trait TreeVisibility {
+    type VisibleTrees: Iterator<Item = Option<Tree>>;
+    type ViewingDistances: Iterator<Item = (Direction, usize)>;
+
+    fn get_trees_in_direction(&self, direction: &Direction) -> Self::VisibleTrees;
+    fn is_visible_from(&self, direction: &Direction) -> bool;
+    fn get_viewing_distance(&self, direction: &Direction) -> usize;
+    fn get_viewing_distances(&self) -> Self::ViewingDistances;
+}
+
+impl TreeVisibility for ForestGridTree {
+    type VisibleTrees = Box<dyn Iterator<Item = Option<Tree>>>;
+    type ViewingDistances = Box<dyn Iterator<Item = (Direction, usize)>>;
+
+    fn get_trees_in_direction(&self, direction: &Direction) -> Self::VisibleTrees {
+        match self.connections.get(direction) {
+            Some(tree) => {
+                match tree.upgrade() {
+                    Some(t) => {
+                        let current_tree = t.borrow();
+                        return Box::new(iter::once(Some(Tree { height: current_tree.height })).chain(current_tree.get_trees_in_direction(direction)));
+                    }
+                    None => Box::new(iter::once(None))
+                }
+            },
+            None => {
+                return Box::new(iter::once(None));
+            }
+        }
+    }
+
+    fn get_viewing_distance(&self, direction: &Direction) -> usize {
+        let trees_in_direction: Vec<Tree> = self.get_trees_in_direction(direction).flatten().collect::<Vec<Tree>>();
+        let number_of_visible_trees = trees_in_direction.iter().take_while(|p| p.height < self.height).count();
+
+        if number_of_visible_trees < trees_in_direction.len() {
+            return number_of_visible_trees + 1;
+        }
+        return number_of_visible_trees;
+    }
+
+    fn is_visible_from(&self, direction: &Direction) -> bool {
+        return self.get_trees_in_direction(direction).flatten().all(|t| t.height < self.height);
+    }
+
+    fn get_viewing_distances(&self) -> Self::ViewingDistances {
+        let directions: [Direction; 4] = [Direction::Above, Direction::Right, Direction::Below, Direction::Left];
+        return Box::new(directions.map(|d| (d, self.get_viewing_distance(&d))).into_iter());
+    }
+}
+
+trait ScenicScore {
+    fn get_scenic_score(&self) -> i64;
+}
+
+impl ScenicScore for ForestGridTree {
+    fn get_scenic_score(&self) -> i64 {
+        self.get_viewing_distances().map(|(_, distance)| distance as i64).product()
+    }
+}
+
+
+pub struct ForestGrid {
+    trees: Vec<Rc<RefCell<ForestGridTree>>>,
+}
+
+impl ForestGrid {
+    /// Checks that `input` is a rectangle of digits before building the grid,
+    /// which [`ForestGrid::from`] assumes.
+    pub fn parse(input: &str) -> Result<Self, ParseError> {
+        let mut width: Option<usize> = None;
+
+        for line in input.lines() {
+            if let Some((index, height)) = line.char_indices().find(|(_, c)| !c.is_ascii_digit()) {
+                return Err(ParseError::spanning(input, &line[index..index + height.len_utf8()], format!("Expected a tree height from 0 to 9 but found {:?}", height)));
+            }
+
+            match width {
+                Some(expected) if expected != line.len() => {
+                    return Err(ParseError::spanning(input, line, format!("Expected a row of {} trees but found {}", expected, line.len())));
+                },
+                _ => width = Some(line.len()),
+            }
+        }
+
+        if width.unwrap_or(0) == 0 {
+            return Err(ParseError::new("The forest does not contain any trees"));
+        }
+
+        return Ok(ForestGrid::from(input));
+    }
+
+    fn from(input: &str) -> Self {
+        let rows_of_trees = input.lines().map(|f|{
+            return f.bytes().map(|c| Rc::new(RefCell::new(ForestGridTree::new(c - b'0')))).collect::<Vec<Rc<RefCell<ForestGridTree>>>>();
+        }).collect::<Vec<Vec<Rc<RefCell<ForestGridTree>>>>>();
+
+        let zipped = rows_of_trees.iter().zip(rows_of_trees.iter().skip(1)).map(|(tree_row, tree_row_below)| tree_row.iter().zip(tree_row_below));
+
+        zipped.flatten().for_each(|(tree_above,tree_below)| {
+            let tree_below_weak = Rc::downgrade(tree_below);
+            let tree_above_weak = Rc::downgrade(tree_above);
+
+            tree_above.borrow_mut().add_connection(Direction::Below, tree_below_weak);
+            tree_below.borrow_mut().add_connection(Direction::Above, tree_above_weak);
+        });
+
+        rows_of_trees.iter().for_each(|tree_row| {
+            tree_row.iter().zip(tree_row.iter().skip(1)).for_each(|(left_tree, right_tree)| {
+                let tree_left_weak = Rc::downgrade(left_tree);
+                let tree_right_weak = Rc::downgrade(right_tree);
+
+                left_tree.borrow_mut().add_connection(Direction::Right, tree_right_weak);
+                right_tree.borrow_mut().add_connection(Direction::Left, tree_left_weak);
+            });
+        });
+
+
+        let flattened_trees =  rows_of_trees.into_iter().flatten().collect::<Vec<Rc<RefCell<ForestGridTree>>>>();
+        return ForestGrid { trees: flattened_trees };
+    }
+
+    fn get_number_of_visible_trees(&self) -> usize {
+        let directions: [Direction; 4] = [Direction::Above, Direction::Right, Direction::Below, Direction::Left];
+
+        self.trees.iter().filter(|t| directions
+            .iter()
+            .any(|d| t
+                .borrow()
+                .is_visible_from(d))).count()
+    }
+
+    fn get_max_scenic_score(&self) -> i64 {
+        return self.trees.iter().map(|t| t.borrow().get_scenic_score()).max().unwrap();
+    }
+}
+
+pub struct Puzzle;
+
+impl Solution for Puzzle {
+    type Parsed = ForestGrid;
+
+    const DAY: u8 = 8;
+
+    fn default_input() -> PathBuf {
+        return Path::new(env!("CARGO_MANIFEST_DIR")).join("input");
+    }
+
+    /// A bad row always fails the parse, since skipping it would change which
+    /// trees are visible.
+    fn parse_with(input: &str, _recovery: &mut Recovery) -> Result<ForestGrid, ParseError> {
+        return ForestGrid::parse(input);
+    }
+
+    fn part1(forest: &ForestGrid) -> Result<Answer, Error> {
+        return Ok(forest.get_number_of_visible_trees().into());
+    }
+
+    fn part2(forest: &ForestGrid) -> Result<Answer, Error> {
+        return Ok(forest.get_max_scenic_score().into());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_core::answers::assert_recorded;
+    use aoc_core::Part;
+
+    const INPUT: &str = include_str!("../input");
+
+    const SAMPLE: &str = include_str!("../sample");
+
+    #[test]
+    fn test_part1_sample() {
+        assert_eq!( Puzzle::solve(Part::One, SAMPLE).unwrap(), Answer::from(21));
+    }
+
+    #[test]
+    fn test_part1_input() {
+        assert_recorded::<Puzzle>(Part::One, INPUT);
+    }
+
+    #[test]
+    fn test_part2_sample() {
+        assert_eq!( Puzzle::solve(Part::Two, SAMPLE).unwrap(), Answer::from(8));
+    }
+
+    #[test]
+    fn test_part2_input(){
+        assert_recorded::<Puzzle>(Part::Two, INPUT);
+    }
+
+    #[test]
+    fn can_calculate_viewing_distance_based_on_first_example_for_sample(){
+        let forest = ForestGrid::from(SAMPLE);
+        let tree = forest.trees[7].borrow();
+        let total_viewing_distance: Vec<(Direction, usize)> = tree.get_viewing_distances().collect();
+
+        assert_eq!(total_viewing_distance, vec![(Direction::Above, 1), (Direction::Right, 2), (Direction::Below, 2), (Direction::Left, 1)]);
+    }
+
+    #[test]
+    fn can_calculate_scenic_score_based_on_first_example_for_sample(){
+        let forest = ForestGrid::from(SAMPLE);
+        let tree = forest.trees[7].borrow();
+        assert_eq!(tree.get_scenic_score(), 4);
+    }
+
+    #[test]
+    fn can_calculate_viewing_distance_based_on_second_example_for_sample(){
+        let forest = ForestGrid::from(SAMPLE);
+        let tree = forest.trees[17].borrow();
+        let total_viewing_distance: Vec<(Direction, usize)> = tree.get_viewing_distances().collect();
+
+        assert_eq!(total_viewing_distance, vec![(Direction::Above, 2), (Direction::Right, 2), (Direction::Below, 1), (Direction::Left, 2)]);
+    }
+
+    #[test]
+    fn can_calculate_scenic_score_based_on_second_example_for_sample(){
+        let forest = ForestGrid::from(SAMPLE);
+        let tree = forest.trees[17].borrow();
+        assert_eq!(tree.get_scenic_score(), 8);
+    }
+
+    #[test]
+    fn parses_forest_from_sample(){
+        let forest = ForestGrid::from(SAMPLE);
+        assert_eq!(forest.trees.len(), 25);
+    }
+
+    #[test]
+    fn reports_rows_of_different_lengths() {
+        let err = ForestGrid::parse("30373\n2552\n65332").err().unwrap();
+        let span = err.span.unwrap();
+
+        assert_eq!((span.line, span.column, span.text.as_str()), (2, 1, "2552"));
+    }
+
+
+}
+
+